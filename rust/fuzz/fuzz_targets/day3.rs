@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+#![no_main]
+
+use advent_2021::{days::day3::Day3, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let solution = Day3;
+    if let Ok(parsed) = solution.parse(&mut &data[..]) {
+        let _ = solution.part1(&parsed);
+        let _ = solution.part2(&parsed);
+    }
+});
@@ -1,20 +1,97 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use clap::Parser;
+use advent_2021::solution::Parts;
+use clap::{CommandFactory, FromArgMatches, Parser};
+
+use crate::{config::Config, output::Format};
 
 #[derive(Parser)]
 #[clap(about, author, version)]
 pub(crate) struct App {
     #[clap(subcommand)]
-    cmd: crate::days::Command,
+    cmd: Command,
+    /// How to print the answers
+    #[clap(short, long, arg_enum, global = true, default_value = "plain")]
+    format: Format,
+    /// Report how long parsing and solving took
+    #[clap(short, long, global = true)]
+    time: bool,
+    /// Which parts to solve
+    #[clap(long, global = true, default_value = "both", possible_values = ["1", "2", "both"])]
+    part: Parts,
+    /// Solve the day again whenever its input changes
+    #[clap(short, long, global = true)]
+    watch: bool,
+    /// Report errors with a backtrace of where they happened
+    #[clap(short, long, global = true)]
+    verbose: bool,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    #[clap(flatten)]
+    Day(crate::commands::Command),
+    RunAll(crate::run_all::RunAll),
+    Bench(crate::bench::Bench),
+    Fetch(crate::fetch::Fetch),
+    Generate(crate::generate::Generate),
+    Submit(crate::submit::Submit),
+    Verify(crate::verify::Verify),
+    #[clap(subcommand)]
+    Config(crate::config::ConfigCommand),
+    Completions(crate::completions::Completions),
+    Manpage(crate::manpage::Manpage),
 }
 
-pub(crate) fn parse_options() -> App {
-    App::parse()
+/// Parses the command line using the settings in `config` as the defaults.
+pub(crate) fn parse_options(config: &Config) -> App {
+    let matches = config.apply(App::command()).get_matches();
+    App::from_arg_matches(&matches).unwrap_or_else(|error| error.exit())
 }
 
 impl App {
-    pub(crate) fn run(self) -> anyhow::Result<()> {
-        self.cmd.run()
+    pub(crate) fn verbose(&self) -> bool {
+        self.verbose
+    }
+
+    /// Whether the command needs `advent.toml`, so it has to fail when the file is broken.
+    pub(crate) fn uses_config(&self) -> bool {
+        matches!(
+            self.cmd,
+            Command::Day(_)
+                | Command::RunAll(_)
+                | Command::Submit(_)
+                | Command::Verify(_)
+                | Command::Config(_)
+        )
+    }
+
+    pub(crate) fn run(self, config: &Config) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !self.watch || matches!(self.cmd, Command::Day(_)),
+            "--watch only works when solving a single day"
+        );
+        match self.cmd {
+            Command::Day(day) => day.run(self.format, self.time, self.part, self.watch),
+            Command::RunAll(run_all) => run_all.run(self.format, self.time, self.part, config),
+            Command::Bench(bench) => bench.run(self.part),
+            Command::Fetch(fetch) => fetch.run(),
+            Command::Generate(generate) => generate.run(),
+            Command::Submit(submit) => submit.run(config),
+            Command::Verify(verify) => verify.run(self.format, self.part, config),
+            Command::Config(command) => command.run(config, self.format),
+            Command::Completions(completions) => completions.run(),
+            Command::Manpage(manpage) => manpage.run(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_do_not_conflict() {
+        App::command().debug_assert();
     }
 }
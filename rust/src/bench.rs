@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::time::Duration;
+
+use advent_2021::solution::Parts;
+use anyhow::Result;
+use clap::Parser;
+
+use crate::commands::Command;
+
+#[derive(Parser)]
+#[clap(about = "Solve a day repeatedly and report how long each phase took")]
+pub(crate) struct Bench {
+    #[clap(short, long, default_value = "10")]
+    runs: usize,
+    #[clap(subcommand)]
+    day: Command,
+}
+
+impl Bench {
+    pub(crate) fn run(self, parts: Parts) -> Result<()> {
+        let timings = (0..self.runs)
+            .map(|_| Ok(self.day.solve(parts, false)?.timings))
+            .collect::<Result<Vec<_>>>()?;
+
+        println!("Benchmarked {} over {} runs", self.day.name(), self.runs);
+        println!("{:<8}{:>14}{:>14}{:>14}", "Phase", "Min", "Median", "Max");
+        let phases = [
+            ("parse", timings.iter().map(|t| t.parse).collect()),
+            ("part 1", timings.iter().filter_map(|t| t.part1).collect()),
+            ("part 2", timings.iter().filter_map(|t| t.part2).collect()),
+        ];
+        for (name, durations) in phases {
+            if let Some((min, median, max)) = summarize(durations) {
+                println!(
+                    "{:<8}{:>14}{:>14}{:>14}",
+                    name,
+                    format!("{:?}", min),
+                    format!("{:?}", median),
+                    format!("{:?}", max)
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn summarize(mut durations: Vec<Duration>) -> Option<(Duration, Duration, Duration)> {
+    durations.sort_unstable();
+    let (first, last) = (durations.first()?, durations.last()?);
+    let middle = durations.len() / 2;
+    let median = if durations.len() % 2 == 0 {
+        (durations[middle - 1] + durations[middle]) / 2
+    } else {
+        durations[middle]
+    };
+    Some((*first, median, *last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarize_finds_the_extrema_and_median() {
+        let expected_summary = Some((
+            Duration::from_millis(1),
+            Duration::from_millis(3),
+            Duration::from_millis(9),
+        ));
+        let durations = [9, 1, 3, 4, 2].map(Duration::from_millis);
+        let summary = summarize(durations.to_vec());
+        assert_eq!(summary, expected_summary);
+    }
+
+    #[test]
+    fn summarize_averages_the_middle_pair_for_even_counts() {
+        let expected_median = Duration::from_millis(5);
+        let durations = [2, 4, 6, 8].map(Duration::from_millis);
+        let (_, median, _) = summarize(durations.to_vec()).unwrap();
+        assert_eq!(median, expected_median);
+    }
+
+    #[test]
+    fn summarize_needs_at_least_one_run() {
+        let summary = summarize(Vec::new());
+        assert_eq!(summary, None);
+    }
+}
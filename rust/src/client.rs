@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    fs,
+    path::PathBuf,
+    thread::sleep,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Args;
+
+use crate::paths;
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+/// Time to wait between requests, so the server is not hammered by repeated runs.
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Args)]
+pub(crate) struct ClientOptions {
+    /// The server to talk to (e.g., a local stub server for testing)
+    #[clap(long, env = "AOC_BASE_URL", default_value = "https://adventofcode.com")]
+    base_url: String,
+    /// The session cookie; read from `session` in the config directory when not set
+    #[clap(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+}
+
+/// A client for the Advent of Code website that authenticates with the user’s session cookie and
+/// limits how often it sends requests.
+pub(crate) struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    state_dir: PathBuf,
+}
+
+impl Client {
+    pub(crate) fn new(options: ClientOptions, state_dir: PathBuf) -> Result<Client> {
+        let session = match options.session {
+            Some(session) => session,
+            None => Self::read_session()?,
+        };
+        Ok(Client {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: options.base_url.trim_end_matches('/').to_string(),
+            session,
+            state_dir,
+        })
+    }
+
+    fn read_session() -> Result<String> {
+        let path = paths::config_dir()?.join("session");
+        let session = fs::read_to_string(&path).with_context(|| {
+            format!(
+                "No session was given with AOC_SESSION or --session, and {} could not be read",
+                path.display()
+            )
+        })?;
+        Ok(session.trim().to_string())
+    }
+
+    pub(crate) fn day_url(&self, day: u8, rest: &str) -> String {
+        format!("{}/2021/day/{}{}", self.base_url, day, rest)
+    }
+
+    pub(crate) fn get(&self, url: &str) -> Result<String> {
+        self.wait_for_turn()?;
+        let request = self.agent.get(url).set("Cookie", &self.cookie());
+        Self::into_body(url, request.call())
+    }
+
+    pub(crate) fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.wait_for_turn()?;
+        let request = self.agent.post(url).set("Cookie", &self.cookie());
+        Self::into_body(url, request.send_form(form))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn into_body(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String> {
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(code, response)) => Err(anyhow!(
+                "The request to {} failed with {} {}",
+                url,
+                code,
+                response.status_text()
+            )),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Sleeps until at least `MIN_REQUEST_INTERVAL` has passed since the last request, then records
+    /// the time of this one.
+    fn wait_for_turn(&self) -> Result<()> {
+        let last_request = self.state_dir.join("last-request");
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        if let Ok(previous) = fs::read_to_string(&last_request) {
+            let previous = Duration::from_secs(previous.trim().parse().unwrap_or(0));
+            if let Some(remaining) = (previous + MIN_REQUEST_INTERVAL).checked_sub(now) {
+                eprintln!("Waiting {:?} before sending the next request.", remaining);
+                sleep(remaining);
+            }
+        }
+        fs::create_dir_all(&self.state_dir)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        fs::write(last_request, now.as_secs().to_string())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::*;
+
+    /// Serves a single request with the given status and body, then returns the request it got.
+    pub(crate) fn stub_server(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+            let response = format!(
+                "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+
+    pub(crate) fn stub_client(base_url: String, state_dir: PathBuf) -> Client {
+        let options = ClientOptions {
+            base_url,
+            session: Some("s3cr3t".to_string()),
+        };
+        Client::new(options, state_dir).unwrap()
+    }
+
+    #[test]
+    fn get_sends_the_session_cookie() -> Result<()> {
+        let state = tempfile::tempdir()?;
+        let (base_url, server) = stub_server(200, "3,4,3,1,2\n");
+        let client = stub_client(base_url, state.path().to_path_buf());
+        let body = client.get(&client.day_url(6, "/input"))?;
+        let request = server.join().unwrap();
+        assert_eq!(body, "3,4,3,1,2\n");
+        assert!(request.starts_with("GET /2021/day/6/input HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=s3cr3t\r\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn error_statuses_are_reported() -> Result<()> {
+        let state = tempfile::tempdir()?;
+        let (base_url, server) = stub_server(404, "Not Found");
+        let client = stub_client(base_url, state.path().to_path_buf());
+        let result = client.get(&client.day_url(26, "/input"));
+        server.join().unwrap();
+        assert!(result.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{num::NonZeroUsize, path::PathBuf};
+
+use advent_2021::days::day1::{self, Aggregate, DepthType, Plot};
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "Sonar Sweep")]
+pub(crate) struct Day1 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+    /// Also count the increases for windows of these sizes (e.g., `2,5,10`)
+    #[clap(long, use_value_delimiter = true)]
+    window: Vec<NonZeroUsize>,
+    /// How each window is summarized before it is compared with the one before it
+    #[clap(short, long, default_value = "sum", possible_values = Aggregate::NAMES)]
+    aggregate: Aggregate,
+    /// Print every window of this size (1 for every reading) with how it changed, then a summary
+    #[clap(long, value_name = "SIZE")]
+    annotate: Option<NonZeroUsize>,
+    /// What to read the depths as; use `i64` for deeper depths or `f64` for fractional ones
+    #[clap(long = "type", name = "type", value_name = "TYPE", default_value = "i32", possible_values = DepthType::NAMES)]
+    depth_type: DepthType,
+    /// Plot the depths and the windows for part 2 to an SVG file, highlighting the increases (`-`
+    /// for sparklines after the answers)
+    #[clap(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    plot: Option<PathBuf>,
+}
+
+impl Day1 {
+    pub(crate) fn solver(&self) -> day1::Day1 {
+        day1::Day1 {
+            windows: self.window.clone(),
+            aggregate: self.aggregate,
+            annotate: self.annotate,
+            depth_type: self.depth_type,
+            plot: self.plot.as_ref().map(|path| {
+                if path.as_os_str() == "-" {
+                    Plot::Sparklines
+                } else {
+                    Plot::Svg(path.clone())
+                }
+            }),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use advent_2021::days::day15;
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "Chiton")]
+pub(crate) struct Day15 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+}
+
+impl Day15 {
+    pub(crate) fn solver(&self) -> day15::Day15 {
+        day15::Day15
+    }
+}
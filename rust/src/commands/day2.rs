@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use advent_2021::days::day2;
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "Dive!")]
+pub(crate) struct Day2 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+}
+
+impl Day2 {
+    pub(crate) fn solver(&self) -> day2::Day2 {
+        day2::Day2
+    }
+}
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use advent_2021::days::day3;
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "Binary Diagnostic")]
+pub(crate) struct Day3 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+}
+
+impl Day3 {
+    pub(crate) fn solver(&self) -> day3::Day3 {
+        day3::Day3
+    }
+}
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use advent_2021::days::day4;
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "Giant Squid")]
+pub(crate) struct Day4 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+}
+
+impl Day4 {
+    pub(crate) fn solver(&self) -> day4::Day4 {
+        day4::Day4
+    }
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use advent_2021::days::day5;
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "Hydrothermal Venture")]
+pub(crate) struct Day5 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+    /// Print the diagram of the vents after solving
+    #[clap(short, long, action)]
+    print_diagram: bool,
+}
+
+impl Day5 {
+    pub(crate) fn solver(&self) -> day5::Day5 {
+        day5::Day5 {
+            print_diagram: self.print_diagram,
+        }
+    }
+}
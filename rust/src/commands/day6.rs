@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use advent_2021::days::day6;
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "Lanternfish")]
+pub(crate) struct Day6 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+    /// How many days to simulate for part 1
+    #[clap(short, long, default_value = "80")]
+    days: usize,
+    /// How many days to simulate for part 2
+    #[clap(short, long, default_value = "256")]
+    extended_days: usize,
+}
+
+impl Day6 {
+    pub(crate) fn solver(&self) -> day6::Day6 {
+        day6::Day6 {
+            days: self.days,
+            extended_days: self.extended_days,
+        }
+    }
+}
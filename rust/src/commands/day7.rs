@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use advent_2021::days::day7;
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "The Treachery of Whales")]
+pub(crate) struct Day7 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+}
+
+impl Day7 {
+    pub(crate) fn solver(&self) -> day7::Day7 {
+        day7::Day7
+    }
+}
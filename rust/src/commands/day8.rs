@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use advent_2021::days::day8;
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "Seven Segment Search")]
+pub(crate) struct Day8 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+}
+
+impl Day8 {
+    pub(crate) fn solver(&self) -> day8::Day8 {
+        day8::Day8
+    }
+}
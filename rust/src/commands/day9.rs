@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use advent_2021::days::day9;
+use clap::{Parser, ValueHint};
+
+#[derive(Parser)]
+#[clap(about = "Smoke Basin")]
+pub(crate) struct Day9 {
+    /// The input to solve (`-` for stdin); defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub(crate) input: Option<PathBuf>,
+}
+
+impl Day9 {
+    pub(crate) fn solver(&self) -> day9::Day9 {
+        day9::Day9
+    }
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::io::stdout;
+
+use anyhow::Result;
+use clap::{CommandFactory, Parser};
+use clap_complete::Shell;
+
+use crate::app::App;
+
+#[derive(Parser)]
+#[clap(about = "Print the completion script for a shell")]
+pub(crate) struct Completions {
+    /// The shell to complete for (e.g., `bash`, `zsh`, or `fish`)
+    #[clap(value_parser)]
+    shell: Shell,
+}
+
+impl Completions {
+    pub(crate) fn run(self) -> Result<()> {
+        let mut command = App::command();
+        let name = command.get_name().to_string();
+        clap_complete::generate(self.shell, &mut command, name, &mut stdout());
+        Ok(())
+    }
+}
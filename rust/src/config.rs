@@ -0,0 +1,359 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fs,
+    io::{stdout, Write},
+    mem,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::{ArgAction, ArgEnum, Command as ClapCommand, CommandFactory};
+
+use crate::{app::App, commands::Command, output::Format};
+
+const FILE_NAME: &str = "advent.toml";
+
+/// Defaults for the command-line options read from `advent.toml`.  Options given on the command
+/// line still take precedence over them.
+#[derive(Debug, Default)]
+pub(crate) struct Config {
+    path: Option<PathBuf>,
+    format: Option<String>,
+    days: BTreeMap<String, BTreeMap<String, OsString>>,
+}
+
+#[derive(clap::Subcommand)]
+pub(crate) enum ConfigCommand {
+    /// Print the settings from `advent.toml` merged with the defaults for every option
+    Show,
+}
+
+impl Config {
+    /// Finds the nearest `advent.toml` in `dir` or one of its parents.  There doesn’t have to be
+    /// one; every option just keeps its usual default.
+    pub(crate) fn find(dir: &Path) -> Result<Config> {
+        match dir
+            .ancestors()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|path| path.is_file())
+        {
+            Some(path) => Config::load(&path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    fn load(path: &Path) -> Result<Config> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Could not open {}", path.display()))?;
+        Config::parse(path, &contents).with_context(|| format!("Could not load {}", path.display()))
+    }
+
+    fn parse(path: &Path, contents: &str) -> Result<Config> {
+        let mut table: toml::value::Table = toml::from_str(contents)?;
+        let format = match table.remove("format") {
+            Some(toml::Value::String(format)) => {
+                Format::from_str(&format, false).map_err(|error| anyhow!(error))?;
+                Some(format)
+            }
+            Some(value) => return Err(anyhow!("`format` should be a string, not {}", value)),
+            None => None,
+        };
+
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let app = App::command();
+        let mut days = BTreeMap::new();
+        for (name, options) in table {
+            let day = Some(&name)
+                .filter(|name| Command::NAMES.contains(&name.as_str()))
+                .and_then(|name| app.find_subcommand(name))
+                .ok_or_else(|| anyhow!("Unknown day: {}", name))?;
+            let options = match options {
+                toml::Value::Table(options) => options,
+                _ => return Err(anyhow!("`{}` should be a table of options", name)),
+            };
+            let mut values = BTreeMap::new();
+            for (option, value) in options {
+                // Options can be spelled like the flag or like the field, e.g., `print_diagram`.
+                let arg = day
+                    .get_arguments()
+                    .filter(|arg| is_option(arg))
+                    .find(|arg| arg.get_id().replace('_', "-") == option.replace('_', "-"))
+                    .ok_or_else(|| match removed_option(&name, &option) {
+                        Some(reason) => anyhow!("{} no longer has `{}`: {}", name, option, reason),
+                        None => anyhow!("{} has no option named `{}`", name, option),
+                    })?;
+                let id = arg.get_id();
+                let value = match value {
+                    toml::Value::String(input) if id == "input" => base_dir.join(input).into(),
+                    // A list is passed the way it would be written on the command line.
+                    toml::Value::Array(items) => match arg.get_value_delimiter() {
+                        Some(delimiter) => items
+                            .iter()
+                            .map(|item| {
+                                to_string(item).ok_or_else(|| {
+                                    anyhow!("`{}.{}` can’t contain {}", name, option, item)
+                                })
+                            })
+                            .collect::<Result<Vec<_>>>()?
+                            .join(&delimiter.to_string())
+                            .into(),
+                        None => return Err(anyhow!("`{}.{}` takes a single value", name, option)),
+                    },
+                    value => to_string(&value)
+                        .ok_or_else(|| anyhow!("`{}.{}` can’t be {}", name, option, value))?
+                        .into(),
+                };
+                values.insert(id.to_string(), value);
+            }
+            days.insert(name, values);
+        }
+
+        Ok(Config {
+            path: Some(path.to_path_buf()),
+            format,
+            days,
+        })
+    }
+
+    /// Makes the settings the defaults of the matching arguments of `command` and its subcommands.
+    pub(crate) fn apply<'a>(&'a self, mut command: ClapCommand<'a>) -> ClapCommand<'a> {
+        if let Some(format) = &self.format {
+            command = command.mut_arg("format", |arg| arg.default_value(format));
+        }
+        self.apply_to_days(command)
+    }
+
+    fn apply_to_days<'a>(&'a self, mut command: ClapCommand<'a>) -> ClapCommand<'a> {
+        for subcommand in command.get_subcommands_mut() {
+            *subcommand = self.apply_to_days(mem::take(subcommand));
+        }
+        self.apply_to_day(command.get_name().to_string().as_str(), command)
+    }
+
+    /// Makes the settings for the day called `name` the defaults of `command`.
+    pub(crate) fn apply_to_day<'a>(
+        &'a self,
+        name: &str,
+        command: ClapCommand<'a>,
+    ) -> ClapCommand<'a> {
+        match self.days.get(name) {
+            Some(options) => options.iter().fold(command, |command, (id, value)| {
+                command.mut_arg(id.as_str(), |arg| arg.default_value_os(value))
+            }),
+            None => command,
+        }
+    }
+
+    /// Writes the settings as TOML.  `format` is the one in effect after the command line, and
+    /// every day lists the default of each option after `advent.toml` has been applied.
+    fn show(&self, out: &mut dyn Write, format: Format) -> Result<()> {
+        match &self.path {
+            Some(path) => writeln!(out, "# Loaded from {}", path.display())?,
+            None => writeln!(out, "# No {} was found", FILE_NAME)?,
+        }
+        let format = format.to_possible_value().map(|value| value.get_name());
+        writeln!(
+            out,
+            "format = {}",
+            toml::Value::from(format.unwrap_or_default())
+        )?;
+
+        let app = App::command();
+        for name in Command::NAMES {
+            let day = match app.find_subcommand(*name) {
+                Some(day) => day,
+                None => continue,
+            };
+            let settings = self.days.get(*name);
+            let options: Vec<_> = day
+                .get_arguments()
+                .filter(|arg| is_option(arg))
+                .filter_map(|arg| {
+                    let setting = settings.and_then(|settings| settings.get(arg.get_id()));
+                    let value = setting
+                        .map(|value| value.as_os_str())
+                        .or_else(|| arg.get_default_values().first().copied())
+                        .map(|value| value.to_string_lossy());
+                    let value = match value {
+                        _ if matches!(arg.get_action(), ArgAction::SetTrue) => {
+                            toml::Value::from(value.map_or(false, |value| value == "true"))
+                        }
+                        Some(value) => match arg.get_value_delimiter() {
+                            Some(delimiter) if arg.get_id() != "input" => toml::Value::from(
+                                value.split(delimiter).map(from_str).collect::<Vec<_>>(),
+                            ),
+                            _ => from_str(&value),
+                        },
+                        None => return None,
+                    };
+                    Some((arg.get_id(), value))
+                })
+                .collect();
+            if !options.is_empty() {
+                writeln!(out, "\n[{}]", name)?;
+                for (option, value) in options {
+                    writeln!(out, "{} = {}", option, value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ConfigCommand {
+    pub(crate) fn run(self, config: &Config, format: Format) -> Result<()> {
+        match self {
+            ConfigCommand::Show => config.show(&mut stdout().lock(), format),
+        }
+    }
+}
+
+/// Why an option a day used to have is gone, so that old config files get a useful error.
+fn removed_option(day: &str, option: &str) -> Option<&'static str> {
+    match (day, option.replace('_', "-").as_str()) {
+        ("day2", "use-aim") => Some("part 2 always uses the aim and part 1 never does"),
+        _ => None,
+    }
+}
+
+/// A single TOML value as it would be written on the command line.
+fn to_string(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(value) => Some(value.clone()),
+        toml::Value::Integer(value) => Some(value.to_string()),
+        toml::Value::Float(value) => Some(value.to_string()),
+        toml::Value::Boolean(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// The TOML for a value from the command line, which is a number if it looks like one.
+fn from_str(value: &str) -> toml::Value {
+    if let Ok(value) = value.parse::<i64>() {
+        value.into()
+    } else if let Some(value) = value.parse::<f64>().ok().filter(|value| value.is_finite()) {
+        value.into()
+    } else {
+        value.into()
+    }
+}
+
+fn is_option(arg: &clap::Arg) -> bool {
+    !matches!(arg.get_id(), "help" | "version")
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::ArgMatches;
+
+    use super::*;
+
+    fn parse_with(config: &Config, args: &[&str]) -> Result<ArgMatches> {
+        Ok(config.apply(App::command()).try_get_matches_from(args)?)
+    }
+
+    #[test]
+    fn the_nearest_config_file_is_used() -> Result<()> {
+        let root = tempfile::tempdir()?;
+        let project = root.path().join("project");
+        let nested = project.join("src").join("days");
+        fs::create_dir_all(&nested)?;
+        fs::write(root.path().join(FILE_NAME), "format = \"tsv\"\n")?;
+        fs::write(project.join(FILE_NAME), "format = \"json\"\n")?;
+        let config = Config::find(&nested)?;
+        assert_eq!(config.path, Some(project.join(FILE_NAME)));
+        assert_eq!(config.format.as_deref(), Some("json"));
+        Ok(())
+    }
+
+    #[test]
+    fn inputs_are_relative_to_the_config_file() -> Result<()> {
+        let expected_input = Some(OsString::from("/project/inputs/day6.txt"));
+        let path = Path::new("/project").join(FILE_NAME);
+        let config = Config::parse(&path, "[day6]\ninput = \"inputs/day6.txt\"\n")?;
+        assert_eq!(config.days["day6"].get("input"), expected_input.as_ref());
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_options_are_rejected() {
+        let path = Path::new("/project").join(FILE_NAME);
+        let error = Config::parse(&path, "[day2]\naim = true\n").unwrap_err();
+        assert_eq!(error.to_string(), "day2 has no option named `aim`");
+    }
+
+    #[test]
+    fn removed_options_say_what_replaced_them() {
+        let expected_error =
+            "day2 no longer has `use_aim`: part 2 always uses the aim and part 1 never does";
+        let path = Path::new("/project").join(FILE_NAME);
+        let error = Config::parse(&path, "[day2]\nuse_aim = true\n").unwrap_err();
+        assert_eq!(error.to_string(), expected_error);
+    }
+
+    #[test]
+    fn settings_become_the_defaults() -> Result<()> {
+        let expected_output = "\
+# Loaded from /project/advent.toml
+format = \"json\"
+
+[day1]
+aggregate = \"sum\"
+type = \"i32\"
+
+[day5]
+input = \"/project/vents.txt\"
+print-diagram = true
+
+[day6]
+days = 18
+extended-days = 256
+";
+        let path = Path::new("/project").join(FILE_NAME);
+        let config = Config::parse(
+            &path,
+            "format = \"json\"\n[day5]\ninput = \"vents.txt\"\nprint-diagram = true\n[day6]\ndays = 18\n",
+        )?;
+        let mut out = Vec::new();
+        config.show(&mut out, Format::Json)?;
+        assert_eq!(String::from_utf8(out)?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn lists_are_joined_like_the_command_line() -> Result<()> {
+        let path = Path::new("/project").join(FILE_NAME);
+        let config = Config::parse(&path, "[day1]\nwindow = [2, 5]\n")?;
+        let matches = parse_with(&config, &["advent_2021", "day1"])?;
+        let day1 = matches.subcommand_matches("day1").unwrap();
+        let windows: Vec<_> = day1.values_of("window").unwrap().collect();
+        assert_eq!(windows, ["2", "5"]);
+        let mut out = Vec::new();
+        config.show(&mut out, Format::Plain)?;
+        assert!(String::from_utf8(out)?.contains("\n[day1]\nwindow = [2, 5]\n"));
+
+        let error = Config::parse(&path, "[day6]\ndays = [1, 2]\n").unwrap_err();
+        assert_eq!(error.to_string(), "`day6.days` takes a single value");
+        Ok(())
+    }
+
+    #[test]
+    fn the_command_line_overrides_the_settings() -> Result<()> {
+        let path = Path::new("/project").join(FILE_NAME);
+        let config = Config::parse(&path, "format = \"json\"\n[day6]\ndays = 18\n")?;
+
+        let matches = parse_with(&config, &["advent_2021", "day6"])?;
+        let day6 = matches.subcommand_matches("day6").unwrap();
+        assert_eq!(matches.value_of("format"), Some("json"));
+        assert_eq!(day6.value_of("days"), Some("18"));
+
+        let matches = parse_with(&config, &["advent_2021", "-f", "tsv", "day6", "-d", "40"])?;
+        let day6 = matches.subcommand_matches("day6").unwrap();
+        assert_eq!(matches.value_of("format"), Some("tsv"));
+        assert_eq!(day6.value_of("days"), Some("40"));
+        Ok(())
+    }
+}
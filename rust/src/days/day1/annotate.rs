@@ -0,0 +1,182 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{cmp::Ordering, fmt, iter::FromIterator, num::NonZeroUsize};
+
+use super::{Aggregate, Depth, IncreaseCounter, Statistic};
+
+/// How a reading (or window) compares with the one before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    NoPrevious,
+    Increased,
+    Decreased,
+    Unchanged,
+}
+
+impl Change {
+    pub(super) fn between<V: PartialOrd>(previous: V, current: V) -> Change {
+        // Depths that can’t be ordered are rejected when they are parsed.
+        match current.partial_cmp(&previous) {
+            Some(Ordering::Greater) => Change::Increased,
+            Some(Ordering::Less) => Change::Decreased,
+            Some(Ordering::Equal) | None => Change::Unchanged,
+        }
+    }
+}
+
+/// A reading (or the aggregate of a window) along with how it changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Annotation<V> {
+    pub value: V,
+    pub change: Change,
+}
+
+/// The annotation of each full window over some depths, in order.  With a window size of 1, that
+/// is every reading.
+pub struct Annotations<I: Iterator>
+where
+    I::Item: Depth,
+{
+    depths: I,
+    counter: IncreaseCounter<I::Item>,
+}
+
+pub fn annotate<I>(
+    depths: I,
+    window_size: NonZeroUsize,
+    aggregate: Aggregate,
+) -> Annotations<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Depth,
+{
+    Annotations {
+        depths: depths.into_iter(),
+        counter: IncreaseCounter::new(window_size, aggregate),
+    }
+}
+
+impl<I: Iterator> Iterator for Annotations<I>
+where
+    I::Item: Depth,
+{
+    type Item = Annotation<Statistic<<I::Item as Depth>::Sum>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let depth = self.depths.next()?;
+            if let Some(annotation) = self.counter.push(depth) {
+                return Some(annotation);
+            }
+        }
+    }
+}
+
+/// Totals for each kind of change and the most increases in a row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub increases: usize,
+    pub decreases: usize,
+    pub plateaus: usize,
+    pub longest_increasing_run: usize,
+}
+
+impl FromIterator<Change> for Summary {
+    fn from_iter<T: IntoIterator<Item = Change>>(changes: T) -> Self {
+        let mut summary = Summary::default();
+        let mut run = 0;
+        for change in changes {
+            match change {
+                Change::Increased => summary.increases += 1,
+                Change::Decreased => summary.decreases += 1,
+                Change::Unchanged => summary.plateaus += 1,
+                Change::NoPrevious => (),
+            }
+            run = if change == Change::Increased {
+                run + 1
+            } else {
+                0
+            };
+            summary.longest_increasing_run = summary.longest_increasing_run.max(run);
+        }
+        summary
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Change::NoPrevious => "N/A - no previous measurement",
+            Change::Increased => "increased",
+            Change::Decreased => "decreased",
+            Change::Unchanged => "no change",
+        })
+    }
+}
+
+impl<V: fmt::Display> fmt::Display for Annotation<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.value, self.change)
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Increases: {}, decreases: {}, no change: {}, longest increasing run: {}",
+            self.increases, self.decreases, self.plateaus, self.longest_increasing_run
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: [i32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    fn changes(window_size: usize) -> Vec<Change> {
+        let window_size = NonZeroUsize::new(window_size).unwrap();
+        annotate(EXAMPLE_INPUT, window_size, Aggregate::Sum)
+            .map(|annotation| annotation.change)
+            .collect()
+    }
+
+    #[test]
+    fn readings_are_annotated_like_example_1() {
+        use Change::*;
+        let expected_changes = [
+            NoPrevious, Increased, Increased, Increased, Decreased, Increased, Increased,
+            Increased, Decreased, Increased,
+        ];
+        assert_eq!(changes(1), expected_changes);
+    }
+
+    #[test]
+    fn windows_are_annotated_like_example_2() {
+        use Change::*;
+        let expected_output = "607 (N/A - no previous measurement)";
+        let expected_changes = [
+            NoPrevious, Increased, Unchanged, Decreased, Increased, Increased, Increased, Increased,
+        ];
+        let first = annotate(EXAMPLE_INPUT, NonZeroUsize::new(3).unwrap(), Aggregate::Sum).next();
+        assert_eq!(
+            first.map(|first| first.to_string()).as_deref(),
+            Some(expected_output)
+        );
+        assert_eq!(changes(3), expected_changes);
+    }
+
+    #[test]
+    fn summaries_count_each_change_and_the_longest_run() {
+        let expected_summary = Summary {
+            increases: 5,
+            decreases: 1,
+            plateaus: 1,
+            longest_increasing_run: 4,
+        };
+        let summary: Summary = changes(3).into_iter().collect();
+        assert_eq!(summary, expected_summary);
+    }
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use num_traits::{FromPrimitive, Num, ToPrimitive};
+
+/// A type that depths can be read as.
+pub trait Depth: Copy + PartialOrd + FromStr + fmt::Display + fmt::Debug + Send + Sync {
+    /// Wide enough to add up a window of depths without overflowing.
+    type Sum: Num
+        + Copy
+        + PartialOrd
+        + FromPrimitive
+        + ToPrimitive
+        + fmt::Display
+        + fmt::Debug
+        + Send
+        + Sync;
+
+    fn widen(self) -> Self::Sum;
+
+    /// Whether the depth is an actual measurement instead of an infinity or NaN.
+    fn is_finite(self) -> bool;
+}
+
+impl Depth for i32 {
+    type Sum = i64;
+
+    fn widen(self) -> i64 {
+        self.into()
+    }
+
+    fn is_finite(self) -> bool {
+        true
+    }
+}
+
+impl Depth for i64 {
+    type Sum = i128;
+
+    fn widen(self) -> i128 {
+        self.into()
+    }
+
+    fn is_finite(self) -> bool {
+        true
+    }
+}
+
+impl Depth for f64 {
+    type Sum = f64;
+
+    fn widen(self) -> f64 {
+        self
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+}
+
+/// Which `Depth` the input is read as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepthType {
+    I32,
+    I64,
+    F64,
+}
+
+impl DepthType {
+    pub const NAMES: [&'static str; 3] = ["i32", "i64", "f64"];
+}
+
+impl Default for DepthType {
+    fn default() -> Self {
+        DepthType::I32
+    }
+}
+
+impl FromStr for DepthType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "i32" => Ok(DepthType::I32),
+            "i64" => Ok(DepthType::I64),
+            "f64" => Ok(DepthType::F64),
+            _ => Err(anyhow!(
+                "Expected one of {} but got {}",
+                DepthType::NAMES.join(", "),
+                s
+            )),
+        }
+    }
+}
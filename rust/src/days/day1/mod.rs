@@ -1,75 +1,251 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use std::{
-    collections::VecDeque,
     fs::File,
-    io::{BufRead, BufReader},
+    io::{BufRead, BufWriter, Write},
     num::NonZeroUsize,
-    path::PathBuf,
 };
 
-use anyhow::{Error, Result};
-use clap::Parser;
+use anyhow::{Context, Result};
 
-#[derive(Parser)]
-#[clap(about = "Sonar Sweep")]
-pub(crate) struct Day1 {
-    input: PathBuf,
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
+
+pub use self::{
+    annotate::{annotate, Annotation, Annotations, Change, Summary},
+    depth::{Depth, DepthType},
+    plot::{Plot, Profile},
+    window::{Aggregate, SlidingWindow, Statistic},
+};
+
+mod annotate;
+mod depth;
+mod plot;
+mod window;
+
+/// Day 1: Sonar Sweep
+#[derive(Clone, Debug, Default)]
+pub struct Day1 {
+    /// More window sizes to count the increases of, which are written after the answers.
+    pub windows: Vec<NonZeroUsize>,
+    /// How each window is summarized before it is compared with the one before it.
+    pub aggregate: Aggregate,
+    /// The size of the windows to write with how each one changed after the answers (1 for
+    /// every reading), if any.
+    pub annotate: Option<NonZeroUsize>,
+    /// What the depths are read as, e.g., when they are too deep for an `i32` or fractional.
+    pub depth_type: DepthType,
+    /// Where to plot the depths and the windows for part 2, if anywhere.
+    pub plot: Option<Plot>,
 }
 
-impl Day1 {
-    pub(crate) fn run(self) -> Result<()> {
-        let file = File::open(self.input)?;
-        let reader = BufReader::new(file);
-        let lines = parse_lines(reader)?;
-
-        let increases = count_increases(&lines, NonZeroUsize::new(1).unwrap());
-        println!("Times the depth measurement increased: {}", increases);
-
-        let increases = count_increases(&lines, NonZeroUsize::new(3).unwrap());
-        println!(
-            "Times the sliding depth measurement increased: {}",
-            increases
-        );
+/// What was found in a single pass over the depths.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sweep {
+    /// The number of increases for each of `WINDOW_SIZES` followed by each of `Day1::windows`.
+    pub increases: Vec<usize>,
+    /// Every window of the size given by `Day1::annotate` with how it changed.  The values are
+    /// already formatted, because their type depends on `Day1::depth_type`.
+    pub annotations: Vec<Annotation<String>>,
+    /// The depths and the windows for part 2, which are only kept when `Day1::plot` is set.
+    pub profile: Option<Profile>,
+}
+
+/// The sizes of the sliding windows compared in parts 1 and 2.
+const WINDOW_SIZES: [usize; 2] = [1, 3];
+
+impl Solution for Day1 {
+    /// The increases are counted while the input is read, so the depths never have to be in memory
+    /// all at once.  Only the annotations (when they are requested) grow with the input.
+    type Parsed = Sweep;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        match self.depth_type {
+            DepthType::I32 => self.parse_as::<i32>(input),
+            DepthType::I64 => self.parse_as::<i64>(input),
+            DepthType::F64 => self.parse_as::<f64>(input),
+        }
+    }
+
+    fn part1(&self, sweep: &Self::Parsed) -> Result<Answer> {
+        Ok(sweep.increases[0].into())
+    }
+
+    fn part2(&self, sweep: &Self::Parsed) -> Result<Answer> {
+        Ok(sweep.increases[1].into())
+    }
+
+    fn extra_output(&self, sweep: &Self::Parsed, out: &mut dyn Write) -> Result<()> {
+        let extra_increases = &sweep.increases[WINDOW_SIZES.len()..];
+        for (size, count) in self.windows.iter().zip(extra_increases) {
+            writeln!(out, "Window {}: {} increases", size, count)?;
+        }
+        if self.annotate.is_some() {
+            for annotation in &sweep.annotations {
+                writeln!(out, "{}", annotation)?;
+            }
+            let summary: Summary = sweep.annotations.iter().map(|a| a.change).collect();
+            writeln!(out, "{}", summary)?;
+        }
+        match (&self.plot, &sweep.profile) {
+            (Some(Plot::Svg(path)), Some(profile)) => {
+                let file = File::create(path)
+                    .with_context(|| format!("Could not create {}", path.display()))?;
+                let mut file = BufWriter::new(file);
+                profile.write_svg(&mut file)?;
+                file.flush()?;
+                writeln!(out, "Plotted the depths to {}", path.display())?;
+            }
+            (Some(Plot::Sparklines), Some(profile)) => profile.write_sparklines(out)?,
+            _ => (),
+        }
         Ok(())
     }
 }
 
-pub(crate) fn count_increases<'a>(
-    xs: impl IntoIterator<Item = &'a i32> + 'a,
-    window_size: NonZeroUsize,
-) -> usize {
-    fn update_window(window: &mut VecDeque<i32>, value: i32) {
-        for x in window.into_iter() {
-            *x = *x + value;
+impl Day1 {
+    fn parse_as<T: Depth>(&self, input: &mut dyn BufRead) -> Result<Sweep> {
+        let mut counters: Vec<_> = WINDOW_SIZES
+            .iter()
+            .map(|size| NonZeroUsize::new(*size).unwrap())
+            .chain(self.windows.iter().copied())
+            .map(|size| IncreaseCounter::new(size, self.aggregate))
+            .collect();
+        let mut annotator = self
+            .annotate
+            .map(|size| IncreaseCounter::<T>::new(size, self.aggregate));
+        let mut annotations = Vec::new();
+        let mut plotters = self.plot.as_ref().map(|_| {
+            WINDOW_SIZES.map(|size| {
+                IncreaseCounter::<T>::new(NonZeroUsize::new(size).unwrap(), self.aggregate)
+            })
+        });
+        let mut series = [Vec::new(), Vec::new()];
+        for depth in depths(input) {
+            let depth = depth?;
+            for counter in &mut counters {
+                counter.push(depth);
+            }
+            if let Some(annotator) = &mut annotator {
+                annotations.extend(annotator.push(depth).map(|annotation| Annotation {
+                    value: annotation.value.to_string(),
+                    change: annotation.change,
+                }));
+            }
+            for (plotter, series) in plotters.iter_mut().flatten().zip(&mut series) {
+                series.extend(plotter.push(depth).map(|annotation| Annotation {
+                    value: annotation.value.to_f64(),
+                    change: annotation.change,
+                }));
+            }
         }
+        let profile = plotters.map(|_| {
+            let [depths, windows] = series;
+            Profile {
+                depths,
+                windows,
+                windows_label: format!(
+                    "windows of {} depths ({})",
+                    WINDOW_SIZES[1], self.aggregate
+                ),
+            }
+        });
+        Ok(Sweep {
+            increases: counters.iter().map(IncreaseCounter::count).collect(),
+            annotations,
+            profile,
+        })
     }
-    let window_size = window_size.get();
-    let mut it = xs.into_iter();
-    let mut window = VecDeque::with_capacity(window_size);
-    for _ in 0..window_size {
-        if let Some(x) = it.next() {
-            update_window(&mut window, *x);
-            window.push_back(*x);
+}
+
+/// Counts how many times the aggregate of a sliding window of depths increases, one depth at a
+/// time.  Only the depths in the current window are kept.
+#[derive(Clone, Debug)]
+pub struct IncreaseCounter<T: Depth> {
+    window: SlidingWindow<T>,
+    previous: Option<Statistic<T::Sum>>,
+    count: usize,
+}
+
+impl<T: Depth> IncreaseCounter<T> {
+    pub fn new(window_size: NonZeroUsize, aggregate: Aggregate) -> IncreaseCounter<T> {
+        IncreaseCounter {
+            window: SlidingWindow::new(window_size, aggregate),
+            previous: None,
+            count: 0,
         }
     }
-    let mut count = 0;
-    for value in it {
-        if let Some(previous) = window.pop_front() {
-            update_window(&mut window, *value);
-            let current = *window.front().unwrap_or(value);
-            count += (current > previous) as usize;
+
+    /// Adds a depth, returning how the window changed once it is full.
+    pub fn push(&mut self, depth: T) -> Option<Annotation<Statistic<T::Sum>>> {
+        let dropped = self.window.push(depth);
+        let current = self.window.statistic()?;
+        let change = match (self.previous, dropped, self.window.aggregate()) {
+            (None, _, _) => Change::NoPrevious,
+            // Neighbouring windows share every other depth, so comparing the depth that was added
+            // with the one that was dropped is exact even when a floating-point sum has rounded.
+            (Some(_), Some(dropped), Aggregate::Sum | Aggregate::Mean) => {
+                Change::between(dropped, depth)
+            }
+            (Some(previous), _, _) => Change::between(previous, current),
+        };
+        self.count += (change == Change::Increased) as usize;
+        self.previous = Some(current);
+        Some(Annotation {
+            value: current,
+            change,
+        })
+    }
+
+    /// How many times the aggregate increased so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Counts how many times the sum of a sliding window increases.
+pub fn count_increases<'a, T: Depth + 'a>(
+    xs: impl IntoIterator<Item = &'a T> + 'a,
+    window_size: NonZeroUsize,
+) -> usize {
+    let mut counter = IncreaseCounter::new(window_size, Aggregate::Sum);
+    for x in xs {
+        counter.push(*x);
+    }
+    counter.count()
+}
+
+/// Reads the depths from `reader` one line at a time and counts the increases for each of the
+/// window sizes in a single pass.
+pub fn sweep<T: Depth>(
+    reader: impl BufRead,
+    window_sizes: &[NonZeroUsize],
+    aggregate: Aggregate,
+) -> Result<Vec<usize>> {
+    let mut counters: Vec<_> = window_sizes
+        .iter()
+        .map(|size| IncreaseCounter::<T>::new(*size, aggregate))
+        .collect();
+    for depth in depths(reader) {
+        let depth = depth?;
+        for counter in &mut counters {
+            counter.push(depth);
         }
-        window.push_back(*value);
     }
-    count
+    Ok(counters.iter().map(IncreaseCounter::count).collect())
 }
 
-fn parse_lines(reader: impl BufRead) -> Result<Vec<i32>> {
-    reader
-        .lines()
-        .map(|line| line?.parse().map_err(Error::from))
-        .collect()
+/// The depths in `reader`, parsed as they are read.
+pub fn depths<T: Depth>(reader: impl BufRead) -> impl Iterator<Item = Result<T>> {
+    reader.lines().enumerate().map(|(idx, line)| {
+        let line = line?;
+        line.parse()
+            .ok()
+            .filter(|depth: &T| depth.is_finite())
+            .ok_or_else(|| ParseError::new(idx + 1, 1, &line, "a depth").into())
+    })
 }
 
 #[cfg(test)]
@@ -82,11 +258,21 @@ mod tests {
     fn parser_parses_the_file() -> Result<()> {
         let expected_output = EXAMPLE_INPUT;
         let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
-        let result = parse_lines(input.as_bytes())?;
+        let result = depths::<i32>(input.as_bytes()).collect::<Result<Vec<_>>>()?;
         assert_eq!(result, expected_output);
         Ok(())
     }
 
+    #[test]
+    fn parser_reports_the_line_with_a_bad_depth() {
+        let expected_error = ParseError::new(2, 1, "20o", "a depth");
+        let input = "199\n20o\n208\n";
+        let error = depths::<i32>(input.as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
     #[test]
     fn example_1_has_seven_increases() -> Result<()> {
         let expected_increases = 7;
@@ -106,4 +292,159 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn deep_windows_do_not_overflow() {
+        let expected_increases = 1;
+        let depths = [i32::MAX - 1, i32::MAX - 1, i32::MAX - 1, i32::MAX];
+        assert_eq!(
+            count_increases(&depths, NonZeroUsize::new(3).unwrap()),
+            expected_increases
+        );
+    }
+
+    #[test]
+    fn depths_too_deep_for_an_i32_can_be_read_as_i64() -> Result<()> {
+        let expected_increases = vec![1, 1];
+        let input =
+            "9000000000000000000\n9000000000000000000\n9000000000000000000\n9000000000000000001\n";
+        let window_sizes = [1, 3].map(|size| NonZeroUsize::new(size).unwrap());
+        let increases = sweep::<i64>(input.as_bytes(), &window_sizes, Aggregate::Sum)?;
+        assert_eq!(increases, expected_increases);
+        Ok(())
+    }
+
+    #[test]
+    fn fractional_depths_can_be_read_as_f64() -> Result<()> {
+        let expected_output =
+            "1.5 (N/A - no previous measurement)\n2.25 (increased)\n2.25 (no change)\n";
+        let day1 = Day1 {
+            annotate: NonZeroUsize::new(2),
+            aggregate: Aggregate::Mean,
+            depth_type: DepthType::F64,
+            ..Day1::default()
+        };
+        let sweep = day1.parse(&mut "1.0\n2.0\n2.5\n2.0\n".as_bytes())?;
+        let annotations: String = sweep
+            .annotations
+            .iter()
+            .map(|annotation| format!("{}\n", annotation))
+            .collect();
+        assert_eq!(annotations, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn floating_point_sums_do_not_change_when_the_same_depth_is_replaced() {
+        use Change::*;
+        // Adding 1 to 1e16 rounds, so the running sums of these windows drift apart.
+        let expected_changes = [NoPrevious, Unchanged, Decreased];
+        let depths = [1.0, 1e16, 1.0, 0.5];
+        let changes: Vec<_> = annotate(depths, NonZeroUsize::new(2).unwrap(), Aggregate::Sum)
+            .map(|annotation| annotation.change)
+            .collect();
+        assert_eq!(changes, expected_changes);
+    }
+
+    #[test]
+    fn depths_that_are_not_numbers_are_rejected() {
+        let expected_error = ParseError::new(2, 1, "NaN", "a depth");
+        let error = depths::<f64>("1.5\nNaN\n".as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
+    #[test]
+    fn sweeping_counts_every_window_size_in_one_pass() -> Result<()> {
+        let expected_increases = vec![7, 5, 5];
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        let window_sizes = [1, 2, 3].map(|size| NonZeroUsize::new(size).unwrap());
+        let increases = sweep::<i32>(input.as_bytes(), &window_sizes, Aggregate::Sum)?;
+        assert_eq!(increases, expected_increases);
+        Ok(())
+    }
+
+    #[test]
+    fn windows_larger_than_the_sweep_never_increase() -> Result<()> {
+        let expected_increases = vec![0];
+        let window_sizes = [NonZeroUsize::new(4).unwrap()];
+        let increases = sweep::<i32>("1\n2\n3\n".as_bytes(), &window_sizes, Aggregate::Sum)?;
+        assert_eq!(increases, expected_increases);
+        Ok(())
+    }
+
+    #[test]
+    fn windows_can_be_compared_by_their_medians() -> Result<()> {
+        let expected_increases = vec![4];
+        let window_sizes = [NonZeroUsize::new(3).unwrap()];
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        let increases = sweep::<i32>(input.as_bytes(), &window_sizes, Aggregate::Median)?;
+        assert_eq!(increases, expected_increases);
+        Ok(())
+    }
+
+    #[test]
+    fn extra_windows_are_written_after_the_answers() -> Result<()> {
+        let expected_output = "Window 2: 5 increases\nWindow 4: 6 increases\n";
+        let day1 = Day1 {
+            windows: vec![NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap()],
+            aggregate: Aggregate::Sum,
+            annotate: None,
+            depth_type: DepthType::I32,
+            plot: None,
+        };
+        let increases =
+            day1.parse(&mut "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n".as_bytes())?;
+        let mut out = Vec::new();
+        day1.extra_output(&increases, &mut out)?;
+        assert_eq!(String::from_utf8(out)?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn depths_are_only_kept_for_plotting() -> Result<()> {
+        let expected_windows = [607.0, 618.0, 618.0, 617.0, 647.0, 716.0, 769.0, 792.0];
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        let sweep = Day1::default().parse(&mut input.as_bytes())?;
+        assert_eq!(sweep.profile, None);
+
+        let day1 = Day1 {
+            plot: Some(Plot::Sparklines),
+            ..Day1::default()
+        };
+        let profile = day1.parse(&mut input.as_bytes())?.profile.unwrap();
+        let windows: Vec<_> = profile.windows.iter().map(|a| a.value).collect();
+        assert_eq!(profile.depths.len(), 10);
+        assert_eq!(windows, expected_windows);
+        assert_eq!(profile.windows_label, "windows of 3 depths (sum)");
+        Ok(())
+    }
+
+    #[test]
+    fn annotations_are_written_with_a_summary() -> Result<()> {
+        let expected_output = "\
+199 (N/A - no previous measurement)
+200 (increased)
+208 (increased)
+210 (increased)
+200 (decreased)
+207 (increased)
+240 (increased)
+269 (increased)
+260 (decreased)
+263 (increased)
+Increases: 7, decreases: 2, no change: 0, longest increasing run: 3
+";
+        let day1 = Day1 {
+            annotate: NonZeroUsize::new(1),
+            ..Day1::default()
+        };
+        let sweep =
+            day1.parse(&mut "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n".as_bytes())?;
+        let mut out = Vec::new();
+        day1.extra_output(&sweep, &mut out)?;
+        assert_eq!(String::from_utf8(out)?, expected_output);
+        Ok(())
+    }
 }
@@ -0,0 +1,277 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use super::{Annotation, Change};
+
+/// Where the depth profile is plotted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Plot {
+    /// An SVG image written to a file
+    Svg(PathBuf),
+    /// Sparklines written after the answers
+    Sparklines,
+}
+
+/// The depths and the windows for part 2 in order, with how each one changed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Profile {
+    pub depths: Vec<Annotation<f64>>,
+    pub windows: Vec<Annotation<f64>>,
+    /// What each window is, e.g., “windows of 3 depths (sum)”.
+    pub windows_label: String,
+}
+
+const WIDTH: f64 = 800.0;
+const PANEL_HEIGHT: f64 = 180.0;
+const LEFT: f64 = 80.0;
+const RIGHT: f64 = 20.0;
+const TOP: f64 = 40.0;
+const BOTTOM: f64 = 20.0;
+
+/// The characters of a sparkline from the lowest values to the highest.
+const LEVELS: [char; 5] = ['_', '.', '-', '~', '^'];
+const SPARKLINE_WIDTH: usize = 80;
+
+impl Profile {
+    /// Writes the depths and windows as two line charts, one above the other.  Each segment that
+    /// ends with an increase is highlighted.  Long sweeps are squeezed into one point per column
+    /// of the chart like the sparklines.
+    pub fn write_svg(&self, out: &mut dyn Write) -> io::Result<()> {
+        let height = 2.0 * (TOP + PANEL_HEIGHT + BOTTOM);
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            WIDTH, height
+        )?;
+        writeln!(
+            out,
+            "<style>text {{ font: 12px sans-serif; }} path {{ fill: none; stroke-width: 1.5; }} \
+             .steady {{ stroke: #4a6fa5; }} .increase {{ stroke: #d1495b; stroke-width: 2.5; }}</style>"
+        )?;
+        writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#)?;
+        self.write_panel(out, "Depths", &self.depths, 0.0)?;
+        let top = TOP + PANEL_HEIGHT + BOTTOM;
+        let title = capitalize(&self.windows_label);
+        self.write_panel(out, &title, &self.windows, top)?;
+        writeln!(out, "</svg>")
+    }
+
+    fn write_panel(
+        &self,
+        out: &mut dyn Write,
+        title: &str,
+        series: &[Annotation<f64>],
+        top: f64,
+    ) -> io::Result<()> {
+        writeln!(
+            out,
+            r#"<text x="{}" y="{}">{}, with the increases highlighted</text>"#,
+            LEFT,
+            top + TOP / 2.0,
+            escape(title)
+        )?;
+        let (min, max) = match bounds(series) {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        let top = top + TOP;
+        for (value, y) in [(max, top), (min, top + PANEL_HEIGHT)] {
+            writeln!(
+                out,
+                r#"<text x="{}" y="{:.1}" text-anchor="end" dominant-baseline="middle">{}</text>"#,
+                LEFT - 8.0,
+                y,
+                value
+            )?;
+        }
+
+        let plot_width = WIDTH - LEFT - RIGHT;
+        let columns = columns(series, plot_width as usize);
+        let step = plot_width / (columns.len().max(2) - 1) as f64;
+        let point = |idx: usize| {
+            let scaled = if max > min {
+                (columns[idx].0 - min) / (max - min)
+            } else {
+                0.5
+            };
+            (
+                LEFT + idx as f64 * step,
+                top + (1.0 - scaled) * PANEL_HEIGHT,
+            )
+        };
+        let mut steady = PathData::default();
+        let mut increases = PathData::default();
+        for (idx, (_, increased)) in columns.iter().enumerate().skip(1) {
+            let path = if *increased {
+                &mut increases
+            } else {
+                &mut steady
+            };
+            path.segment(idx, point(idx - 1), point(idx));
+        }
+        for (class, path) in [("steady", steady), ("increase", increases)] {
+            if !path.data.is_empty() {
+                writeln!(out, r#"<path class="{}" d="{}"/>"#, class, path.data)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a sparkline of the depths and one of the windows, each followed by a line with a `+`
+    /// under the increases.  Long sweeps are squeezed into `SPARKLINE_WIDTH` columns.
+    pub fn write_sparklines(&self, out: &mut dyn Write) -> io::Result<()> {
+        for (title, series) in [
+            ("Depths", &self.depths),
+            (&self.windows_label, &self.windows),
+        ] {
+            let (min, max) = match bounds(series) {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+            writeln!(out, "{} from {} to {}:", capitalize(title), min, max)?;
+            let columns = columns(series, SPARKLINE_WIDTH);
+            let line: String = columns
+                .iter()
+                .map(|(mean, _)| {
+                    let scaled = if max > min {
+                        (mean - min) / (max - min)
+                    } else {
+                        0.0
+                    };
+                    LEVELS[(scaled * (LEVELS.len() - 1) as f64).round() as usize]
+                })
+                .collect();
+            let markers: String = columns
+                .iter()
+                .map(|(_, increased)| if *increased { '+' } else { ' ' })
+                .collect();
+            writeln!(out, "{}", line)?;
+            writeln!(out, "{}", markers.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits `series` into at most `width` columns, each with the mean of its values and whether most
+/// of them increased.  When `series` fits, each column is just one value and whether it increased.
+fn columns(series: &[Annotation<f64>], width: usize) -> Vec<(f64, bool)> {
+    let count = series.len().min(width);
+    (0..count)
+        .map(|column| {
+            let values =
+                &series[column * series.len() / count..(column + 1) * series.len() / count];
+            let mean = values.iter().map(|a| a.value).sum::<f64>() / values.len() as f64;
+            let increases = values
+                .iter()
+                .filter(|a| a.change == Change::Increased)
+                .count();
+            (mean, 2 * increases > values.len())
+        })
+        .collect()
+}
+
+fn bounds(series: &[Annotation<f64>]) -> Option<(f64, f64)> {
+    let values = series.iter().map(|a| a.value);
+    let min = values.clone().reduce(f64::min)?;
+    let max = values.reduce(f64::max)?;
+    Some((min, max))
+}
+
+/// The `d` attribute of an SVG path made of line segments, which joins each segment onto the last
+/// one when they meet.
+#[derive(Default)]
+struct PathData {
+    data: String,
+    end: Option<usize>,
+}
+
+impl PathData {
+    fn segment(&mut self, idx: usize, (x1, y1): (f64, f64), (x2, y2): (f64, f64)) {
+        if self.end != Some(idx - 1) {
+            if !self.data.is_empty() {
+                self.data.push(' ');
+            }
+            self.data.push_str(&format!("M{:.1},{:.1}", x1, y1));
+        }
+        self.data.push_str(&format!(" L{:.1},{:.1}", x2, y2));
+        self.end = Some(idx);
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> Profile {
+        let series = |values: &[f64]| {
+            values
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| Annotation {
+                    value: *value,
+                    change: match idx {
+                        0 => Change::NoPrevious,
+                        _ if *value > values[idx - 1] => Change::Increased,
+                        _ if *value < values[idx - 1] => Change::Decreased,
+                        _ => Change::Unchanged,
+                    },
+                })
+                .collect()
+        };
+        Profile {
+            depths: series(&[1.0, 2.0, 2.0, 5.0, 3.0]),
+            windows: series(&[5.0, 9.0, 10.0]),
+            windows_label: "windows of 3 depths (sum)".to_string(),
+        }
+    }
+
+    #[test]
+    fn sparklines_mark_the_increases() -> anyhow::Result<()> {
+        let expected_output = "\
+Depths from 1 to 5:
+_..^-
+ + +
+Windows of 3 depths (sum) from 5 to 10:
+_~^
+ ++
+";
+        let mut out = Vec::new();
+        profile().write_sparklines(&mut out)?;
+        assert_eq!(String::from_utf8(out)?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn svgs_highlight_the_segments_that_increase() -> anyhow::Result<()> {
+        let expected_paths = [
+            r#"<path class="steady" d="M255.0,175.0 L430.0,175.0 M605.0,40.0 L780.0,130.0"/>"#,
+            r#"<path class="increase" d="M80.0,220.0 L255.0,175.0 M430.0,175.0 L605.0,40.0"/>"#,
+        ];
+        let mut out = Vec::new();
+        profile().write_svg(&mut out)?;
+        let svg = String::from_utf8(out)?;
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        for path in expected_paths {
+            assert!(svg.lines().any(|line| line == path), "missing {}", path);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{cmp::Ordering, collections::VecDeque, fmt, num::NonZeroUsize, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use num_traits::{FromPrimitive, Num, One, ToPrimitive, Zero};
+
+use super::Depth;
+
+/// How the values in a window are summarized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Median,
+}
+
+impl Aggregate {
+    pub const NAMES: [&'static str; 5] = ["sum", "mean", "min", "max", "median"];
+}
+
+impl Default for Aggregate {
+    fn default() -> Self {
+        Aggregate::Sum
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sum" => Ok(Aggregate::Sum),
+            "mean" => Ok(Aggregate::Mean),
+            "min" => Ok(Aggregate::Min),
+            "max" => Ok(Aggregate::Max),
+            "median" => Ok(Aggregate::Median),
+            _ => Err(anyhow!(
+                "Expected one of {} but got {}",
+                Aggregate::NAMES.join(", "),
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Aggregate::Sum => "sum",
+            Aggregate::Mean => "mean",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+            Aggregate::Median => "median",
+        })
+    }
+}
+
+/// The aggregate of a window.  Means and medians are kept as fractions, so windows can be compared
+/// without rounding.
+#[derive(Clone, Copy, Debug)]
+pub struct Statistic<S> {
+    numerator: S,
+    denominator: S,
+}
+
+impl<S: Num + Copy + ToPrimitive> Statistic<S> {
+    fn new(numerator: S, denominator: S) -> Statistic<S> {
+        Statistic {
+            numerator,
+            denominator,
+        }
+    }
+
+    pub fn to_f64(self) -> f64 {
+        let to_f64 = |value: S| value.to_f64().unwrap_or(f64::NAN);
+        to_f64(self.numerator) / to_f64(self.denominator)
+    }
+}
+
+impl<S: Num> From<S> for Statistic<S> {
+    fn from(value: S) -> Self {
+        Statistic {
+            numerator: value,
+            denominator: S::one(),
+        }
+    }
+}
+
+impl<S: Num + Copy + PartialOrd> PartialOrd for Statistic<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // The denominators are always positive and no larger than the window, so cross-multiplying
+        // keeps the order without overflowing the sum type.
+        let lhs = self.numerator * other.denominator;
+        let rhs = other.numerator * self.denominator;
+        lhs.partial_cmp(&rhs)
+    }
+}
+
+impl<S: Num + Copy + PartialOrd> PartialEq for Statistic<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<S: Num + Copy + ToPrimitive + fmt::Display> fmt::Display for Statistic<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if (self.numerator % self.denominator).is_zero() {
+            write!(f, "{}", self.numerator / self.denominator)
+        } else {
+            write!(f, "{}", self.to_f64())
+        }
+    }
+}
+
+/// The last `size` values of a stream.  Its aggregate is updated as each value is pushed instead
+/// of being recalculated from the whole window.
+#[derive(Clone, Debug)]
+pub struct SlidingWindow<T: Depth> {
+    size: usize,
+    aggregate: Aggregate,
+    values: VecDeque<T>,
+    // The sum is kept as a wider type so that deep enough depths can’t overflow it.
+    sum: T::Sum,
+    // The values that can still become the minimum (or maximum) in the order they were pushed.  A
+    // value is dropped once a later one is smaller (or larger), so the front is always the extreme.
+    extrema: VecDeque<T>,
+    // The values in sorted order, which is only kept for medians.
+    sorted: Vec<T>,
+}
+
+impl<T: Depth> SlidingWindow<T> {
+    pub fn new(size: NonZeroUsize, aggregate: Aggregate) -> SlidingWindow<T> {
+        SlidingWindow {
+            size: size.get(),
+            aggregate,
+            values: VecDeque::with_capacity(size.get()),
+            sum: T::Sum::zero(),
+            extrema: VecDeque::new(),
+            sorted: Vec::new(),
+        }
+    }
+
+    pub fn aggregate(&self) -> Aggregate {
+        self.aggregate
+    }
+
+    /// Adds a value to the window, dropping the oldest one once the window is full.  The dropped
+    /// value is returned.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let oldest = if self.values.len() == self.size {
+            self.values.pop_front()
+        } else {
+            None
+        };
+        if let Some(oldest) = oldest {
+            self.remove(oldest);
+        }
+        self.values.push_back(value);
+        self.add(value);
+        oldest
+    }
+
+    /// The aggregate of the values in the window, or `None` until the window is full.
+    pub fn statistic(&self) -> Option<Statistic<T::Sum>> {
+        if self.values.len() < self.size {
+            return None;
+        }
+        let statistic = match self.aggregate {
+            Aggregate::Sum => self.sum.into(),
+            Aggregate::Mean => {
+                let size = T::Sum::from_usize(self.size).expect("the window size fits in its sum");
+                Statistic::new(self.sum, size)
+            }
+            Aggregate::Min | Aggregate::Max => self.extrema[0].widen().into(),
+            Aggregate::Median => {
+                let middle = self.size / 2;
+                if self.size % 2 == 1 {
+                    self.sorted[middle].widen().into()
+                } else {
+                    let sum = self.sorted[middle - 1].widen() + self.sorted[middle].widen();
+                    Statistic::new(sum, T::Sum::one() + T::Sum::one())
+                }
+            }
+        };
+        Some(statistic)
+    }
+
+    fn add(&mut self, value: T) {
+        self.sum = self.sum + value.widen();
+        let is_superseded: fn(T, T) -> bool = match self.aggregate {
+            Aggregate::Min => |candidate, value| candidate > value,
+            Aggregate::Max => |candidate, value| candidate < value,
+            Aggregate::Median => {
+                let idx = self.sorted.partition_point(|x| *x < value);
+                self.sorted.insert(idx, value);
+                return;
+            }
+            Aggregate::Sum | Aggregate::Mean => return,
+        };
+        while let Some(candidate) = self.extrema.back() {
+            if !is_superseded(*candidate, value) {
+                break;
+            }
+            self.extrema.pop_back();
+        }
+        self.extrema.push_back(value);
+    }
+
+    fn remove(&mut self, value: T) {
+        self.sum = self.sum - value.widen();
+        match self.aggregate {
+            Aggregate::Min | Aggregate::Max => {
+                if self.extrema.front() == Some(&value) {
+                    self.extrema.pop_front();
+                }
+            }
+            Aggregate::Median => {
+                let idx = self.sorted.partition_point(|x| *x < value);
+                self.sorted.remove(idx);
+            }
+            Aggregate::Sum | Aggregate::Mean => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUES: [i32; 8] = [5, 1, 4, 4, 9, 2, 6, 3];
+
+    fn statistics(size: usize, aggregate: Aggregate) -> Vec<Statistic<i64>> {
+        let mut window = SlidingWindow::new(NonZeroUsize::new(size).unwrap(), aggregate);
+        VALUES
+            .iter()
+            .filter_map(|value| {
+                window.push(*value);
+                window.statistic()
+            })
+            .collect()
+    }
+
+    /// Recalculates each statistic from scratch to check the incremental ones against.
+    fn naive_statistics(size: usize, aggregate: Aggregate) -> Vec<Statistic<i64>> {
+        VALUES
+            .windows(size)
+            .map(|window| {
+                let sum: i64 = window.iter().map(|x| i64::from(*x)).sum();
+                let mut sorted = window.to_vec();
+                sorted.sort_unstable();
+                match aggregate {
+                    Aggregate::Sum => sum.into(),
+                    Aggregate::Mean => Statistic::new(sum, size as i64),
+                    Aggregate::Min => i64::from(sorted[0]).into(),
+                    Aggregate::Max => i64::from(sorted[size - 1]).into(),
+                    Aggregate::Median if size % 2 == 1 => i64::from(sorted[size / 2]).into(),
+                    Aggregate::Median => {
+                        let middle = i64::from(sorted[size / 2 - 1] + sorted[size / 2]);
+                        Statistic::new(middle, 2)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn aggregates_match_recalculating_every_window() {
+        for aggregate in Aggregate::NAMES.map(|name| name.parse::<Aggregate>().unwrap()) {
+            for size in 1..=VALUES.len() {
+                let expected_statistics = naive_statistics(size, aggregate);
+                let result = statistics(size, aggregate);
+                assert_eq!(result, expected_statistics, "{:?} of {}", aggregate, size);
+            }
+        }
+    }
+
+    #[test]
+    fn windows_have_no_statistic_until_they_are_full() {
+        let mut window = SlidingWindow::new(NonZeroUsize::new(2).unwrap(), Aggregate::Sum);
+        window.push(1);
+        assert_eq!(window.statistic(), None);
+        window.push(2);
+        assert_eq!(window.statistic(), Some(Statistic::from(3_i64)));
+    }
+
+    #[test]
+    fn fractions_are_compared_exactly() {
+        let expected_output = "2.5";
+        let half = Statistic::new(5_i64, 2);
+        assert!(half > Statistic::from(2) && half < Statistic::from(3));
+        assert_eq!(Statistic::new(4_i64, 2), Statistic::from(2));
+        assert_eq!(half.to_string(), expected_output);
+    }
+}
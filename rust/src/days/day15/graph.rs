@@ -4,22 +4,23 @@ use std::{cmp::Reverse, ops::Index};
 
 use priority_queue::PriorityQueue;
 
-#[derive(Debug, PartialEq)]
-pub struct Point {
-    row: usize,
-    column: usize,
-    value: u8,
-}
-
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Graph {
     nodes: Vec<u8>,
-    pub(crate) rows: usize,
-    pub(crate) columns: usize,
+    rows: usize,
+    columns: usize,
 }
 
 impl Graph {
-    pub(crate) fn shortest_path_cost(
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn shortest_path_cost(
         &self,
         (start_row, start_column): (usize, usize),
         (goal_row, goal_column): (usize, usize),
@@ -86,7 +87,7 @@ impl Graph {
         distances[idx(goal_row, goal_column)]
     }
 
-    pub(crate) fn grow(&mut self, row_multiplier: usize, col_multiplier: usize) {
+    pub fn grow(&mut self, row_multiplier: usize, col_multiplier: usize) {
         fn wrap(value: u8) -> u8 {
             1 + (value - 1) % 9
         }
@@ -102,7 +103,7 @@ impl Graph {
         for row_index in 0..=row_multiplier {
             let row_offset = row_index * self.rows;
             for row in 0..self.rows {
-                let offset = row * self.rows;
+                let offset = row * self.columns;
                 for col_index in 0..=col_multiplier {
                     let col_offset = col_index * self.columns;
                     let target_slice: Vec<_> = self.nodes[offset..(offset + self.columns)]
@@ -222,4 +223,12 @@ mod tests {
         graph.grow(1, 2);
         assert_eq!(graph, expected_graph);
     }
+
+    #[test]
+    fn graphs_that_are_not_square_can_be_extended() {
+        let expected_graph = Graph::from([[1, 2, 3], [4, 5, 6], [2, 3, 4], [5, 6, 7]]);
+        let mut graph = Graph::from([[1, 2, 3], [4, 5, 6]]);
+        graph.grow(1, 0);
+        assert_eq!(graph, expected_graph);
+    }
 }
@@ -2,37 +2,98 @@
 
 mod graph;
 
-use std::path::PathBuf;
+use std::io::BufRead;
 
 use anyhow::Result;
-use clap::Parser;
 
-use crate::util::read_input;
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+    util::read_input,
+};
 
-use self::graph::Graph;
+pub use self::graph::Graph;
 
-#[derive(Parser)]
-#[clap(about = "Chiton")]
-pub(crate) struct Day15 {
-    input: PathBuf,
-}
+/// Day 15: Chiton
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day15;
 
 impl Day15 {
-    pub(crate) fn run(self) -> Result<()> {
-        let input = read_input(self.input)?;
-        let input: Vec<Vec<u8>> = input
-            .lines()
-            .map(|s| s.chars().map(|ch| ch.to_digit(10).unwrap() as u8).collect())
-            .collect();
-        let mut input = Graph::from(input);
-
-        let cavern_risk = input.shortest_path_cost((0, 0), (input.rows - 1, input.columns - 1));
-        println!("The lowest cavern risk cost: {}", cavern_risk);
-
-        input.grow(4, 4);
-        let cave_risk = input.shortest_path_cost((0, 0), (input.rows - 1, input.columns - 1));
-        println!("The lowest cave risk cost: {}", cave_risk);
-
-        Ok(())
+    fn lowest_risk(graph: &Graph) -> Answer {
+        graph
+            .shortest_path_cost((0, 0), (graph.rows() - 1, graph.columns() - 1))
+            .into()
+    }
+}
+
+impl Solution for Day15 {
+    type Parsed = Graph;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        let input = read_input(input)?;
+        let lines: Vec<&str> = input.lines().collect();
+        let risks = lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(column, ch)| {
+                        let risk = ch.to_digit(10).filter(|risk| *risk > 0);
+                        risk.map(|risk| risk as u8).ok_or_else(|| {
+                            ParseError::new(idx + 1, column + 1, line, "a risk level from 1 to 9")
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<u8>>, _>>()?;
+        let width = match risks.first() {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => return Err(ParseError::new(1, 1, "", "a row of risk levels").into()),
+        };
+        if let Some(idx) = risks.iter().position(|row| row.len() != width) {
+            let line = lines[idx];
+            let column = width.min(line.len()) + 1;
+            let expected = "a row of risk levels as long as the first";
+            return Err(ParseError::new(idx + 1, column, line, expected).into());
+        }
+        Ok(Graph::from(risks))
+    }
+
+    fn part1(&self, cavern: &Self::Parsed) -> Result<Answer> {
+        Ok(Self::lowest_risk(cavern))
+    }
+
+    fn part2(&self, cavern: &Self::Parsed) -> Result<Answer> {
+        let mut cave = cavern.clone();
+        cave.grow(4, 4);
+        Ok(Self::lowest_risk(&cave))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_must_be_as_long_as_the_first() {
+        let expected_error =
+            ParseError::new(2, 3, "11", "a row of risk levels as long as the first");
+        let error = Day15.parse(&mut "116\n11\n".as_bytes()).unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
+    #[test]
+    fn risk_levels_start_at_1() {
+        let expected_error = ParseError::new(1, 2, "106", "a risk level from 1 to 9");
+        let error = Day15.parse(&mut "106\n".as_bytes()).unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
+    #[test]
+    fn empty_inputs_are_rejected() {
+        let expected_error = ParseError::new(1, 1, "", "a row of risk levels");
+        let error = Day15.parse(&mut "".as_bytes()).unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
     }
 }
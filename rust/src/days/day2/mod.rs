@@ -1,37 +1,40 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::{fs::File, io::BufReader, path::PathBuf};
+use std::io::BufRead;
 
 use anyhow::Result;
-use clap::Parser;
 
-use crate::days::day2::program::Program;
+use crate::solution::{Answer, Solution};
 
 mod program;
 
-#[derive(Parser)]
-#[clap(about = "Dive!")]
-pub(crate) struct Day2 {
-    input: PathBuf,
-    #[clap(short, long)]
-    multiply_results: bool,
-    #[clap(short, long)]
-    use_aim: bool,
-}
+pub use self::program::{Program, State};
+
+/// Day 2: Dive!
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day2;
 
 impl Day2 {
-    pub fn run(self) -> Result<()> {
-        let file = File::open(self.input)?;
-        let reader = BufReader::new(file);
-        let program = Program::parse(reader, self.use_aim)?;
-        let result = program.run();
-        println!(
-            "Position: \t{}\nDepth:\t\t{}",
-            result.position, result.depth
-        );
-        if self.multiply_results {
-            println!("Multiplied together: {}", result.position * result.depth);
-        }
-        Ok(())
+    fn answer(program: &Program, use_aim: bool) -> Answer {
+        let result = program.run(use_aim);
+        Answer::from(i128::from(result.position) * i128::from(result.depth))
+            .with("position", result.position)
+            .with("depth", result.depth)
+    }
+}
+
+impl Solution for Day2 {
+    type Parsed = Program;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        Program::parse(input)
+    }
+
+    fn part1(&self, program: &Self::Parsed) -> Result<Answer> {
+        Ok(Self::answer(program, false))
+    }
+
+    fn part2(&self, program: &Self::Parsed) -> Result<Answer> {
+        Ok(Self::answer(program, true))
     }
 }
@@ -2,21 +2,24 @@
 
 use std::{collections::HashMap, io::BufRead};
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use nom::{
-    character::complete::{alpha1, digit1, space1},
-    combinator::{map_opt, map_res},
-    sequence::separated_pair,
+    character::complete::{alpha1, digit1, space0, space1},
+    combinator::{eof, map_opt, map_res},
+    error::context,
+    sequence::{separated_pair, terminated, tuple},
 };
 
+use crate::parse::{IResult, ParseError};
+
 #[derive(Default, Debug, PartialEq)]
-pub(crate) struct State {
-    pub(crate) position: i32,
-    pub(crate) depth: i32,
-    pub(crate) aim: i32,
+pub struct State {
+    pub position: i64,
+    pub depth: i64,
+    pub aim: i64,
 }
 
-pub(crate) struct Program(Vec<Box<dyn Fn(&mut State)>>);
+pub struct Program(Vec<(&'static str, u16)>);
 
 impl Program {
     fn command_mapping(
@@ -28,21 +31,21 @@ impl Program {
                     "forward",
                     Box::new(|number: u16| {
                         Box::new(move |state: &mut State| {
-                            state.position += number as i32;
-                            state.depth += number as i32 * state.aim;
+                            state.position += i64::from(number);
+                            state.depth += i64::from(number) * state.aim;
                         }) as Box<dyn Fn(&mut State)>
                     }) as Box<dyn Fn(u16) -> Box<dyn Fn(&mut State)>>,
                 ),
                 (
                     "down",
                     Box::new(|number: u16| {
-                        Box::new(move |state: &mut State| state.aim += number as i32)
+                        Box::new(move |state: &mut State| state.aim += i64::from(number))
                     }),
                 ),
                 (
                     "up",
                     Box::new(|number: u16| {
-                        Box::new(move |state: &mut State| state.aim -= number as i32)
+                        Box::new(move |state: &mut State| state.aim -= i64::from(number))
                     }),
                 ),
             ])
@@ -51,48 +54,49 @@ impl Program {
                 (
                     "forward",
                     Box::new(|number: u16| {
-                        Box::new(move |state: &mut State| state.position += number as i32)
+                        Box::new(move |state: &mut State| state.position += i64::from(number))
                             as Box<dyn Fn(&mut State)>
                     }) as Box<dyn Fn(u16) -> Box<dyn Fn(&mut State)>>,
                 ),
                 (
                     "down",
                     Box::new(|number: u16| {
-                        Box::new(move |state: &mut State| state.depth += number as i32)
+                        Box::new(move |state: &mut State| state.depth += i64::from(number))
                     }),
                 ),
                 (
                     "up",
                     Box::new(|number: u16| {
-                        Box::new(move |state: &mut State| state.depth -= number as i32)
+                        Box::new(move |state: &mut State| state.depth -= i64::from(number))
                     }),
                 ),
             ])
         }
     }
 
-    pub(crate) fn parse(reader: impl BufRead, use_aim: bool) -> Result<Program> {
-        let cmds = Program::command_mapping(use_aim);
+    pub fn parse(reader: impl BufRead) -> Result<Program> {
+        let cmds = Program::command_mapping(false);
 
         let raw_program: Result<Vec<_>> = reader
             .lines()
-            .map(|line| {
+            .enumerate()
+            .map(|(idx, line)| {
                 let line = line?;
 
                 let mut parser = {
-                    fn distance(input: &str) -> nom::IResult<&str, u16> {
-                        map_res(digit1, |s: &str| s.parse())(input)
+                    fn distance(input: &str) -> IResult<&str, u16> {
+                        context("a distance", map_res(digit1, |s: &str| s.parse()))(input)
                     }
-                    let command = map_opt(alpha1, |str| cmds.contains_key(str).then(|| str));
-                    separated_pair(command, space1, distance)
-                };
-
-                let command = {
-                    let (_, (command, value)) =
-                        parser(line.as_ref()).map_err(|e| anyhow!("{}", e))?;
-                    cmds[command](value)
+                    let command = context(
+                        "forward, down, or up",
+                        map_opt(alpha1, |str| cmds.get_key_value(str).map(|(k, _)| *k)),
+                    );
+                    let end = context("the end of the line", tuple((space0, eof)));
+                    terminated(separated_pair(command, space1, distance), end)
                 };
 
+                let (_, command) = parser(line.as_ref())
+                    .map_err(|e| ParseError::from_nom(line.as_str(), e).on_line(idx + 1))?;
                 Ok(command)
             })
             .collect();
@@ -100,11 +104,14 @@ impl Program {
         raw_program.map(Program)
     }
 
-    pub(crate) fn run(&self) -> State {
-        self.0.iter().fold(State::default(), |mut state, command| {
-            command(&mut state);
-            state
-        })
+    pub fn run(&self, use_aim: bool) -> State {
+        let cmds = Program::command_mapping(use_aim);
+        self.0
+            .iter()
+            .fold(State::default(), |mut state, (command, value)| {
+                cmds[command](*value)(&mut state);
+                state
+            })
     }
 }
 
@@ -123,8 +130,8 @@ mod tests {
             state
         };
         let input = EXAMPLE_PROGRAM;
-        let program = Program::parse(input.as_bytes(), false)?;
-        let result = program.run();
+        let program = Program::parse(input.as_bytes())?;
+        let result = program.run(false);
         assert_eq!(result, expected_state);
         Ok(())
     }
@@ -137,9 +144,17 @@ mod tests {
             aim: 10,
         };
         let input = EXAMPLE_PROGRAM;
-        let program = Program::parse(input.as_bytes(), true)?;
-        let result = program.run();
+        let program = Program::parse(input.as_bytes())?;
+        let result = program.run(true);
         assert_eq!(result, expected_state);
         Ok(())
     }
+
+    #[test]
+    fn unknown_commands_are_reported() {
+        let expected_error = ParseError::new(2, 1, "backward 5", "forward, down, or up");
+        let input = "forward 5\nbackward 5\n";
+        let error = Program::parse(input.as_bytes()).err().unwrap();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
 }
@@ -1,34 +1,35 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::{fs::File, io::BufReader, path::PathBuf};
+use std::io::BufRead;
 
 use anyhow::Result;
-use clap::Parser;
+use num_bigint::BigUint;
 
-use crate::days::day3::report::{Report, ReportExt};
+use crate::solution::{Answer, Solution};
 
 mod report;
 
-#[derive(Parser)]
-#[clap(about = "Binary Diagnostic")]
-pub(crate) struct Day3 {
-    input: PathBuf,
-}
+pub use self::report::{RatingError, Report, ReportExt};
 
-impl Day3 {
-    pub(crate) fn run(self) -> Result<()> {
-        let stream = File::open(self.input)?;
-        let reader = BufReader::new(stream);
-        let report = Report::parse(reader)?;
+/// Day 3: Binary Diagnostic
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day3;
 
-        let (gamma, epsilon) = report.extrema();
-        let power_consumption = report.power_consumption();
-        println!("Gamma: {}, Epsilon: {}", gamma, epsilon);
-        println!("Power Consumption: {}", power_consumption);
+impl Solution for Day3 {
+    type Parsed = Report<Vec<BigUint>, BigUint>;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        Report::parse(input)
+    }
 
-        let life_support_rating = report.life_support_rating();
-        println!("Life Support Rating: {}", life_support_rating);
+    fn part1(&self, report: &Self::Parsed) -> Result<Answer> {
+        let (gamma, epsilon) = report.extrema();
+        Ok(Answer::from(report.power_consumption())
+            .with("gamma", gamma)
+            .with("epsilon", epsilon))
+    }
 
-        Ok(())
+    fn part2(&self, report: &Self::Parsed) -> Result<Answer> {
+        Ok(report.life_support_rating()?.into())
     }
 }
@@ -1,17 +1,17 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::{
-    borrow::Borrow,
-    io::{BufRead, Error},
-    marker::PhantomData,
-};
+use std::{borrow::Borrow, fmt, io::BufRead, marker::PhantomData};
 
 use anyhow::Result;
 use num_bigint::BigUint;
 use num_traits::{One, Zero};
 
+use crate::parse::ParseError;
+
+const MAX_WIDTH: usize = 32;
+
 #[derive(Debug, PartialEq)]
-pub(crate) struct Report<B: Borrow<Vec<I>>, I: Borrow<BigUint>> {
+pub struct Report<B: Borrow<Vec<I>>, I: Borrow<BigUint>> {
     data: B,
     width: usize,
     bi: PhantomData<I>,
@@ -29,7 +29,7 @@ impl<B: Borrow<Vec<I>>, I: Borrow<BigUint>> Report<B, I> {
         }
     }
 
-    pub(crate) fn extrema(&self) -> (usize, usize) {
+    pub fn extrema(&self) -> (usize, usize) {
         let xs = self.data.borrow();
         let ones_threshold = xs.len() / 2 - (1 - xs.len() % 2);
         xs.iter()
@@ -45,12 +45,12 @@ impl<B: Borrow<Vec<I>>, I: Borrow<BigUint>> Report<B, I> {
             })
     }
 
-    pub(crate) fn power_consumption(&self) -> usize {
+    pub fn power_consumption(&self) -> usize {
         let (gamma, epsilon) = self.extrema();
         gamma * epsilon
     }
 
-    pub(crate) fn life_support_rating(&self) -> usize {
+    pub fn life_support_rating(&self) -> Result<usize, RatingError> {
         let num_bits = self.width;
         let (oxygen, co2) = (0..num_bits).rev().fold(
             (
@@ -64,14 +64,46 @@ impl<B: Borrow<Vec<I>>, I: Borrow<BigUint>> Report<B, I> {
                 )
             },
         );
-        if oxygen.len() == 1 && co2.len() == 1 {
-            decode(&oxygen[0]) * decode(&co2[0])
+        fn rating(rating: &'static str, numbers: Vec<&BigUint>) -> Result<usize, RatingError> {
+            match numbers.as_slice() {
+                [number] => Ok(decode(number)),
+                _ => Err(RatingError {
+                    rating,
+                    remaining: numbers.len(),
+                }),
+            }
+        }
+        Ok(rating("oxygen generator", oxygen)? * rating("CO₂ scrubber", co2)?)
+    }
+}
+
+/// The bit criteria for a rating did not narrow the report down to exactly one number.
+#[derive(Debug, PartialEq)]
+pub struct RatingError {
+    pub rating: &'static str,
+    pub remaining: usize,
+}
+
+impl fmt::Display for RatingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.remaining == 0 {
+            write!(
+                f,
+                "No numbers in the report meet the bit criteria for the {} rating",
+                self.rating
+            )
         } else {
-            panic!("The life support rating did not converge.  Santa is doomed! ☠️ 🎅");
+            write!(
+                f,
+                "{} numbers in the report meet the bit criteria for the {} rating, but only one should",
+                self.remaining, self.rating
+            )
         }
     }
 }
 
+impl std::error::Error for RatingError {}
+
 fn filter_numbers_meeting_criteria(
     src: Vec<&BigUint>,
     index: usize,
@@ -100,17 +132,33 @@ fn decode(i: &BigUint) -> usize {
         .fold(0, |acc, x| acc << 1 | x as usize)
 }
 
-pub(crate) trait ReportExt {
-    fn parse(input: impl BufRead) -> Result<Report<Vec<BigUint>, BigUint>, Error>;
+pub trait ReportExt {
+    fn parse(input: impl BufRead) -> Result<Report<Vec<BigUint>, BigUint>>;
 }
 
 impl ReportExt for Report<Vec<BigUint>, BigUint> {
-    fn parse(input: impl BufRead) -> Result<Report<Vec<BigUint>, BigUint>, Error> {
-        let result: Result<Vec<_>, Error> = input
+    fn parse(input: impl BufRead) -> Result<Report<Vec<BigUint>, BigUint>> {
+        let result: Result<Vec<_>> = input
             .lines()
-            .map(|s| Ok(BigUint::new(s?.chars().rev().map(to_u32).collect())))
+            .enumerate()
+            .map(|(idx, line)| {
+                let line = line?;
+                if let Some(column) = line.chars().position(|ch| !matches!(ch, '0' | '1')) {
+                    return Err(ParseError::new(idx + 1, column + 1, &line, "‘0’ or ‘1’").into());
+                }
+                // The ratings are decoded into a `usize`, and their product has to fit in one too.
+                if line.len() > MAX_WIDTH {
+                    let expected = format!("the end of a number of at most {} bits", MAX_WIDTH);
+                    return Err(ParseError::new(idx + 1, MAX_WIDTH + 1, &line, expected).into());
+                }
+                Ok(BigUint::new(line.chars().rev().map(to_u32).collect()))
+            })
             .collect();
-        Ok(Report::new(result?))
+        let result = result?;
+        if result.is_empty() {
+            return Err(ParseError::new(1, 1, "", "a binary number").into());
+        }
+        Ok(Report::new(result))
     }
 }
 
@@ -127,6 +175,23 @@ mod tests {
     const INPUT: &str =
         "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n";
 
+    #[test]
+    fn report_parsing_nothing_is_an_error() {
+        let expected_error = ParseError::new(1, 1, "", "a binary number");
+        let error = Report::parse("".as_bytes()).unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
+    #[test]
+    fn report_parsing_rejects_numbers_that_are_too_wide() {
+        let line = "1".repeat(33);
+        let expected_error =
+            ParseError::new(2, 33, &line, "the end of a number of at most 32 bits");
+        let input = format!("101\n{}\n", line);
+        let error = Report::parse(input.as_bytes()).unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
     #[test]
     fn report_parsing_single_number_is_just_that_number() -> Result<()> {
         let expected = Report::new(vec![BigUint::from_str("4294967296")?]);
@@ -146,6 +211,13 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn report_parsing_rejects_digits_other_than_0_and_1() {
+        let expected_error = ParseError::new(2, 3, "102", "‘0’ or ‘1’");
+        let error = Report::parse("10\n102".as_bytes()).unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
     #[test]
     fn report_querying_finds_the_minimum() -> Result<()> {
         let expected_minimum = 0b01001;
@@ -195,8 +267,32 @@ mod tests {
     fn example_2_life_support_rating_is_230() -> Result<()> {
         let expected_consumption = 230;
         let report = Report::parse(INPUT.as_bytes())?;
-        let result = report.life_support_rating();
+        let result = report.life_support_rating()?;
         assert_eq!(result, expected_consumption);
         Ok(())
     }
+
+    #[test]
+    fn duplicate_numbers_are_reported() -> Result<()> {
+        let expected_error = Err(RatingError {
+            rating: "oxygen generator",
+            remaining: 2,
+        });
+        let report = Report::parse("101\n101\n".as_bytes())?;
+        let result = report.life_support_rating();
+        assert_eq!(result, expected_error);
+        Ok(())
+    }
+
+    #[test]
+    fn numbers_that_all_share_a_bit_are_reported() -> Result<()> {
+        let expected_error = Err(RatingError {
+            rating: "CO₂ scrubber",
+            remaining: 0,
+        });
+        let report = Report::parse("110\n111\n".as_bytes())?;
+        let result = report.life_support_rating();
+        assert_eq!(result, expected_error);
+        Ok(())
+    }
 }
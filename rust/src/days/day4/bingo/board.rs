@@ -4,13 +4,15 @@ use anyhow::{anyhow, Result};
 use nom::{
     character::complete::{digit1, newline, space0},
     combinator::map_res,
+    error::context,
     multi::many_m_n,
     sequence::{preceded, terminated},
-    IResult,
 };
 
+use crate::parse::IResult;
+
 #[derive(Debug, PartialEq)]
-pub(crate) struct Board {
+pub struct Board {
     board: [[u8; 5]; 5],
     marked_rows: [u8; 5],
     marked_columns: [u8; 5],
@@ -18,14 +20,21 @@ pub(crate) struct Board {
 }
 
 impl Board {
-    pub(crate) fn parse(input: &str) -> IResult<&str, Board> {
-        let square = map_res(digit1, |s| u8::from_str_radix(s, 10));
+    pub fn parse(input: &str) -> IResult<&str, Board> {
+        let square = context(
+            "a number from 0 to 255",
+            map_res(digit1, |s: &str| s.parse::<u8>()),
+        );
         let line = many_m_n(5, 5, preceded(space0, square));
-        let mut board = map_res(many_m_n(5, 5, terminated(line, newline)), Board::try_from);
+        let end_of_row = context("the end of the row", newline);
+        let mut board = map_res(
+            many_m_n(5, 5, terminated(line, end_of_row)),
+            Board::try_from,
+        );
         board(input)
     }
 
-    pub(crate) fn mark(&mut self, value: u8) {
+    pub fn mark(&mut self, value: u8) {
         if self.winning_mark.is_none() {
             'outer: for (r, row) in self.board.iter().enumerate() {
                 for (c, square) in row.iter().enumerate() {
@@ -51,26 +60,26 @@ impl Board {
         }
     }
 
-    pub(crate) fn is_winner(&self) -> bool {
+    pub fn is_winner(&self) -> bool {
         self.winning_mark.is_some()
     }
 
-    pub(crate) fn score(&self) -> Option<u16> {
+    pub fn score(&self) -> Option<u32> {
         let unmarked_rows = self.marked_rows.iter().map(|x| !x);
         let winning_mark = self.winning_mark?;
-        let unmarked_tally = unmarked_rows.enumerate().fold(0u16, |sum, (r, row)| {
+        let unmarked_tally = unmarked_rows.enumerate().fold(0u32, |sum, (r, row)| {
             sum + [0b00001, 0b00010, 0b00100, 0b01000, 0b10000]
                 .into_iter()
                 .enumerate()
-                .fold(0u16, |sum, (c, column)| {
+                .fold(0u32, |sum, (c, column)| {
                     if row & column != 0 {
-                        sum + self.board[r as usize][c] as u16
+                        sum + u32::from(self.board[r as usize][c])
                     } else {
                         sum
                     }
                 })
         });
-        Some(unmarked_tally * winning_mark as u16)
+        Some(unmarked_tally * u32::from(winning_mark))
     }
 
     pub(super) fn new(board: [[u8; 5]; 5]) -> Board {
@@ -296,4 +305,21 @@ mod tests {
         let score = board.score();
         assert_eq!(score, expected_score);
     }
+
+    #[test]
+    fn board_with_large_numbers_reports_the_score_without_overflowing() {
+        let expected_score = Some(1_216_660);
+        let mut board = Board::new([
+            [230, 231, 232, 233, 234],
+            [235, 236, 237, 238, 239],
+            [240, 241, 242, 243, 244],
+            [245, 246, 247, 248, 249],
+            [250, 251, 252, 253, 254],
+        ]);
+        for value in 250..=254 {
+            board.mark(value);
+        }
+        let score = board.score();
+        assert_eq!(score, expected_score);
+    }
 }
@@ -3,22 +3,27 @@
 use nom::{
     bytes::complete::tag,
     character::complete::digit1,
-    combinator::{map, map_res},
+    combinator::{cut, map, map_res},
+    error::context,
     multi::separated_list1,
-    IResult,
 };
 
+use crate::parse::IResult;
+
 #[derive(Debug, Default, PartialEq)]
-pub(crate) struct Draws(pub(super) Vec<u8>);
+pub struct Draws(pub(super) Vec<u8>);
 
 impl Draws {
-    pub(crate) fn parse(input: &str) -> IResult<&str, Draws> {
-        let number = map_res(digit1, |s| u8::from_str_radix(s, 10));
-        let mut number_seq = map(separated_list1(tag(","), number), Draws);
+    pub fn parse(input: &str) -> IResult<&str, Draws> {
+        let number = context(
+            "a number from 0 to 255",
+            map_res(digit1, |s: &str| s.parse::<u8>()),
+        );
+        let mut number_seq = map(separated_list1(tag(","), cut(number)), Draws);
         number_seq(input)
     }
 
-    pub(crate) fn iter(&self) -> impl Iterator<Item = &u8> {
+    pub fn iter(&self) -> impl Iterator<Item = &u8> {
         self.0.iter()
     }
 }
@@ -5,18 +5,20 @@ use std::{
     mem::{swap, take},
 };
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use nom::{
-    character::complete::newline,
-    combinator::map,
-    multi::{many1, separated_list1},
-    sequence::separated_pair,
+    character::complete::{multispace0, newline},
+    combinator::{cut, eof, map, not},
+    error::context,
+    multi::many1,
+    sequence::{preceded, separated_pair, terminated},
 };
 
 use super::{board::Board, draws::Draws};
+use crate::parse::ParseError;
 
 #[derive(Debug, PartialEq)]
-pub(crate) struct Game {
+pub struct Game {
     draws: Draws,
     boards: Vec<Board>,
     winners: Vec<usize>,
@@ -24,14 +26,16 @@ pub(crate) struct Game {
 }
 
 impl Game {
-    pub(crate) fn parse(input: &str) -> Result<Game> {
+    pub fn parse(input: &str) -> Result<Game> {
         let draws = Draws::parse;
-        let boards = separated_list1(newline, Board::parse);
+        let end_of_draws = context("the end of the draws", many1(newline));
+        let board = preceded(not(eof), cut(terminated(Board::parse, multispace0)));
+        let boards = context("a bingo board", many1(board));
         let mut game = map(
-            separated_pair(draws, many1(newline), boards),
+            separated_pair(draws, end_of_draws, boards),
             |(draws, boards)| Game::new(draws, boards),
         );
-        let (_, result) = game(input).map_err(|e| anyhow!("{}", e))?;
+        let (_, result) = game(input).map_err(|e| ParseError::from_nom(input, e))?;
         Ok(result)
     }
 
@@ -44,7 +48,7 @@ impl Game {
         }
     }
 
-    pub(crate) fn mark(&mut self, value: u8) {
+    pub fn mark(&mut self, value: u8) {
         for (idx, board) in self.boards.iter_mut().enumerate() {
             board.mark(value);
             if board.is_winner() && !self.winner_bitmap.contains(&idx) {
@@ -54,13 +58,13 @@ impl Game {
         }
     }
 
-    pub(crate) fn mark_draws(&mut self) {
+    pub fn mark_draws(&mut self) {
         let mut draws = take(&mut self.draws);
         draws.iter().for_each(|draw| self.mark(*draw));
         swap(&mut draws, &mut self.draws);
     }
 
-    pub(crate) fn winners(&self) -> impl Iterator<Item = &Board> {
+    pub fn winners(&self) -> impl Iterator<Item = &Board> {
         self.winners.iter().map(|x| &self.boards[*x])
     }
 }
@@ -81,6 +85,22 @@ mod tests {
         assert_eq!(result.is_err(), true)
     }
 
+    #[test]
+    fn parser_when_a_board_is_wrong_it_reports_where() {
+        let expected_error = ParseError::new(5, 8, " 8  2 2x  4 24", "a number from 0 to 255");
+        let input = concat!(
+            "7,4,9\n",
+            "\n",
+            "22 13 17 11  0\n",
+            "21  9 14 16  7\n",
+            " 8  2 2x  4 24\n",
+            " 6 10  3 18  5\n",
+            " 1 12 20 15 19\n",
+        );
+        let error = Game::parse(input).unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
     #[test]
     fn parser_when_the_input_is_complete_it_returns_a_game() -> Result<()> {
         let expected_result = Game::new(
@@ -339,7 +359,7 @@ mod tests {
             ],
         );
         game.mark_draws();
-        let winning_scores: Option<Vec<u16>> = game.winners().map(Board::score).collect();
+        let winning_scores: Option<Vec<u32>> = game.winners().map(Board::score).collect();
         assert_eq!(winning_scores.unwrap(), expected_wining_scores);
     }
 }
@@ -4,5 +4,5 @@ mod board;
 mod draws;
 mod game;
 
-pub(crate) use board::Board;
-pub(crate) use game::Game;
+pub use board::Board;
+pub use game::Game;
@@ -1,46 +1,48 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::path::PathBuf;
+use std::io::BufRead;
 
 use anyhow::{anyhow, Result};
-use clap::Parser;
 
-use crate::util::read_input;
+use crate::{
+    solution::{Answer, Solution},
+    util::read_input,
+};
 
-use self::bingo::{Board, Game};
+pub use self::bingo::{Board, Game};
 
 mod bingo;
 
-#[derive(Parser)]
-#[clap(about = "Giant Squid")]
-pub(crate) struct Day4 {
-    input: PathBuf,
-}
+/// Day 4: Giant Squid
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day4;
 
 impl Day4 {
-    pub(crate) fn run(self) -> Result<()> {
-        let input = read_input(self.input)?;
-        let mut game = Game::parse(input.as_str())?;
-        game.mark_draws();
-
+    fn score(board: Option<&Board>) -> Result<Answer> {
         let no_winner_error = || anyhow!("Expected a winner but none was found.");
-        let winners: Vec<&Board> = game.winners().collect();
-
-        let my_score = winners
-            .first()
+        let score = board
             .ok_or_else(&no_winner_error)?
             .score()
             .ok_or_else(&no_winner_error)?;
+        Ok(score.into())
+    }
+}
 
-        let squid_score = winners
-            .last()
-            .ok_or_else(&no_winner_error)?
-            .score()
-            .ok_or_else(&no_winner_error)?;
+impl Solution for Day4 {
+    type Parsed = Game;
 
-        println!("The score if Santa wins is: {}", my_score);
-        println!("The score if Santa lets the squid wins is: {}", squid_score);
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        let input = read_input(input)?;
+        let mut game = Game::parse(input.as_str())?;
+        game.mark_draws();
+        Ok(game)
+    }
+
+    fn part1(&self, game: &Self::Parsed) -> Result<Answer> {
+        Self::score(game.winners().next())
+    }
 
-        Ok(())
+    fn part2(&self, game: &Self::Parsed) -> Result<Answer> {
+        Self::score(game.winners().last())
     }
 }
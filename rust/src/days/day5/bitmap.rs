@@ -4,14 +4,14 @@ use std::{fmt::Display, iter::repeat, str};
 
 use anyhow::{ensure, Result};
 
-pub(crate) struct Bitmap {
-    width: u16,
-    height: u16,
+pub struct Bitmap {
+    width: u32,
+    height: u32,
     data: Vec<u8>,
 }
 
 impl Bitmap {
-    pub(crate) fn new(width: u16, height: u16) -> Bitmap {
+    pub fn new(width: u32, height: u32) -> Bitmap {
         Bitmap {
             width,
             height,
@@ -19,31 +19,36 @@ impl Bitmap {
         }
     }
 
-    pub(crate) fn width(&self) -> u16 {
+    pub fn width(&self) -> u32 {
         self.width
     }
 
-    pub(crate) fn height(&self) -> u16 {
+    pub fn height(&self) -> u32 {
         self.height
     }
 
-    pub(crate) fn draw(&mut self, x: u16, y: u16) -> Result<()> {
+    pub fn draw(&mut self, x: u16, y: u16) -> Result<()> {
+        let (x, y) = (u32::from(x), u32::from(y));
         ensure!(x < self.width() && y < self.height());
         let width = self.width() as usize;
-        self.data[x as usize + y as usize * width] += 1;
+        // Only whether more than one line crosses a point matters, so the count stops at 255.
+        let pixel = &mut self.data[x as usize + y as usize * width];
+        *pixel = pixel.saturating_add(1);
         Ok(())
     }
 
-    pub(crate) fn iter<'a>(&'a self) -> impl Iterator<Item = u8> + 'a {
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = u8> + 'a {
         self.data.iter().cloned()
     }
 
-    fn render_span(&self, begin: usize, length: u16, buf: &mut [u8]) {
+    fn render_span(&self, begin: usize, length: u32, buf: &mut [u8]) {
         let length = length as usize;
         for (idx, pixel) in self.data[begin..(begin + length)].iter().enumerate() {
+            // Counts above 9 are drawn as `+` so every byte stays ASCII.
             match pixel {
-                0 => buf[idx] = '.' as u8,
-                _ => buf[idx] = pixel + ('0' as u8),
+                0 => buf[idx] = b'.',
+                1..=9 => buf[idx] = pixel + b'0',
+                _ => buf[idx] = b'+',
             };
         }
     }
@@ -157,6 +162,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn draw_shows_a_plus_when_drawn_more_than_nine_times() -> Result<()> {
+        let expected_output = "+.";
+        let mut bitmap = Bitmap::new(2, 1);
+        for _ in 0..300 {
+            bitmap.draw(0, 0)?;
+        }
+        let output = format!("{}", bitmap);
+        assert_eq!(output, expected_output);
+        Ok(())
+    }
+
     #[test]
     fn iter_gets_an_iterator_to_the_raw_data() -> Result<()> {
         let expected_output = vec![0, 1, 0, 0];
@@ -4,51 +4,66 @@ use std::cmp::{max, max_by_key, min, min_by_key};
 
 use anyhow::Result;
 use nom::{
-    branch::alt,
     bytes::complete::tag,
     character::complete::{digit1, space0},
-    combinator::{eof, map, map_res},
-    error::Error,
-    multi::separated_list1,
+    combinator::map_res,
+    error::context,
     sequence::{delimited, separated_pair},
-    Err, IResult,
 };
 
 use super::bitmap::Bitmap;
+use crate::parse::{lines_of, IResult, ParseError};
 
-#[derive(Debug, PartialEq)]
-pub(crate) struct CommandList(Vec<((u16, u16), (u16, u16))>);
+/// The most points a diagram can have.  Each point takes a byte, so this keeps a diagram to 16 MiB.
+const MAX_AREA: u64 = 1 << 24;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct CommandList(Vec<((u16, u16), (u16, u16))>);
 
 impl CommandList {
-    pub(crate) fn new() -> Self {
+    #[cfg(test)]
+    pub fn new() -> Self {
         CommandList(Vec::new())
     }
 
-    pub(crate) fn parse(input: &str) -> Result<CommandList, Err<Error<&str>>> {
+    pub fn parse(input: &str) -> Result<CommandList, ParseError> {
         fn coordinate_part(input: &str) -> IResult<&str, u16> {
-            map_res(digit1, |src| u16::from_str_radix(src, 10))(input)
+            let number = map_res(digit1, |src: &str| src.parse::<u16>());
+            context("a coordinate from 0 to 65535", number)(input)
         }
         fn point(input: &str) -> IResult<&str, (u16, u16)> {
-            separated_pair(coordinate_part, tag(","), coordinate_part)(input)
+            separated_pair(coordinate_part, context("‘,’", tag(",")), coordinate_part)(input)
         }
-        let arrow = tag("->");
+        let arrow = context("‘->’", tag("->"));
         let command = separated_pair(point, delimited(space0, arrow, space0), point);
-        let mut command_list = alt((
-            map(separated_list1(tag("\n"), command), CommandList),
-            map(eof, |_| CommandList::new()),
-        ));
-        let (_, command_list) = command_list(input)?;
-        Ok(command_list)
+        let (_, commands) = lines_of(command)(input).map_err(|e| ParseError::from_nom(input, e))?;
+
+        // Each command is on a line of its own, so the one at `idx` is on line `idx + 1`.
+        let (mut width, mut height) = (0, 0);
+        for (idx, ((x1, y1), (x2, y2))) in commands.iter().enumerate() {
+            width = max(width, u64::from(max(*x1, *x2)) + 1);
+            height = max(height, u64::from(max(*y1, *y2)) + 1);
+            if width * height > MAX_AREA {
+                let line = input.lines().nth(idx).unwrap_or_default();
+                let expected = format!("a line in a diagram of at most {} points", MAX_AREA);
+                return Err(ParseError::new(idx + 1, 1, line, expected));
+            }
+        }
+        Ok(CommandList(commands))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &((u16, u16), (u16, u16))> {
+        self.0.iter()
     }
 
-    pub(crate) fn apply_commands(&self, bitmap: &mut Bitmap) -> Result<()> {
+    pub fn apply_commands(&self, bitmap: &mut Bitmap) -> Result<()> {
         self.0
             .iter()
             .map(|(p1, p2)| CommandList::draw_line(*p1, *p2, bitmap))
             .collect()
     }
 
-    pub(crate) fn required_bounds(&self) -> (u16, u16) {
+    pub fn required_bounds(&self) -> (u32, u32) {
         let xs = self
             .0
             .iter()
@@ -59,7 +74,9 @@ impl CommandList {
             .iter()
             .map(|((_, y), _)| y)
             .chain(self.0.iter().map(|(_, (_, y))| y));
-        (*xs.max().unwrap_or(&0) + 1, *ys.max().unwrap_or(&0) + 1)
+        // The bounds are one past the largest coordinate, which doesn’t fit in a `u16` for 65535.
+        let bound = |coordinate: Option<&u16>| u32::from(*coordinate.unwrap_or(&0)) + 1;
+        (bound(xs.max()), bound(ys.max()))
     }
 
     fn draw_line((x1, y1): (u16, u16), (x2, y2): (u16, u16), bitmap: &mut Bitmap) -> Result<()> {
@@ -69,17 +86,19 @@ impl CommandList {
             min_by_key((x1, y1), (x2, y2), |(x, _)| *x),
             max_by_key((x1, y1), (x2, y2), |(x, _)| *x),
         );
+        if x1 == x2 {
+            // This also draws a single point, which has no slope.
+            let (y1, y2) = (min(y1, y2), max(y1, y2));
+            return (y1..=y2)
+                .map(|y| bitmap.draw(x1 as u16, y as u16))
+                .collect();
+        }
         let m = (y2 - y1) as f64 / (x2 - x1) as f64;
         if m.abs() <= 1.0 {
             let y0 = y1 as f64 - m * x1 as f64;
             (x1..=x2)
                 .map(|x| bitmap.draw(x as u16, (m * x as f64 + y0).round() as u16))
                 .collect()
-        } else if m.is_infinite() {
-            let (y1, y2) = (min(y1, y2), max(y1, y2));
-            (y1..=y2)
-                .map(|y| bitmap.draw(x1 as u16, y as u16))
-                .collect()
         } else {
             let m = 1.0 / m;
             let x0 = x1 as f64 - m * y1 as f64;
@@ -146,6 +165,25 @@ mod tests {
         assert_eq!(list.is_err(), true);
     }
 
+    #[test]
+    fn parse_reports_where_the_input_is_invalid() {
+        let expected_error = ParseError::new(2, 5, "5,8 => 13,21", "‘->’");
+        let error = CommandList::parse("1,1 -> 2,3\n5,8 => 13,21\n").unwrap_err();
+        assert_eq!(error, expected_error);
+    }
+
+    #[test]
+    fn parse_rejects_lines_too_far_apart_to_draw() {
+        let expected_error = ParseError::new(
+            2,
+            1,
+            "65535,65535 -> 65535,65535",
+            "a line in a diagram of at most 16777216 points",
+        );
+        let error = CommandList::parse("0,0 -> 5,5\n65535,65535 -> 65535,65535\n").unwrap_err();
+        assert_eq!(error, expected_error);
+    }
+
     #[test]
     fn bounds_returns_area_needed_to_draw_all_commands() -> Result<()> {
         let expected_bounds = (640, 480);
@@ -155,6 +193,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn bounds_include_the_largest_coordinate() -> Result<()> {
+        let expected_bounds = (65536, 3);
+        let list = CommandList::parse("65535,1 -> 65535,2")?;
+        let bounds = list.required_bounds();
+        assert_eq!(bounds, expected_bounds);
+        Ok(())
+    }
+
+    #[test]
+    fn command_list_with_a_single_point_draws_it_to_the_bitmap() -> Result<()> {
+        let expected_result = "...
+...
+..1";
+        let input = CommandList::from(vec![((2, 2), (2, 2))]);
+        let mut bitmap = Bitmap::new(3, 3);
+        input.apply_commands(&mut bitmap)?;
+        let result = format!("{}", bitmap);
+        assert_eq!(result, expected_result);
+        Ok(())
+    }
+
     #[test]
     fn command_list_with_a_horizontal_line_draws_it_to_the_bitmap() -> Result<()> {
         let expected_result = "111\n...\n...";
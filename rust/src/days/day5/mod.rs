@@ -1,54 +1,65 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::path::PathBuf;
+use std::io::{BufRead, Write};
 
-use anyhow::{anyhow, Result};
-use clap::Parser;
+use anyhow::Result;
 
-use crate::util::read_input;
+use crate::{
+    solution::{Answer, Solution},
+    util::read_input,
+};
 
-use self::{bitmap::Bitmap, command_list::CommandList};
+pub use self::{bitmap::Bitmap, command_list::CommandList};
 
 mod bitmap;
 mod command_list;
 
-#[derive(Parser)]
-#[clap(about = "Hydrothermal Venture")]
-pub(crate) struct Day5 {
-    input: PathBuf,
-    #[clap(short, long)]
-    ignore_diagonals: bool,
-    #[clap(short, long)]
-    print_diagram: bool,
+/// Day 5: Hydrothermal Venture
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day5 {
+    /// Write the diagram of the vents after the answers.
+    pub print_diagram: bool,
 }
 
 impl Day5 {
-    pub(crate) fn run(self) -> Result<()> {
-        let input = read_input(self.input)?;
-        let commands = {
-            let commands = CommandList::parse(input.as_str()).map_err(|err| anyhow!("{}", err))?;
-            if self.ignore_diagonals {
-                commands
-                    .into_iter()
-                    .filter(|((x1, y1), (x2, y2))| x1 != x2 && y1 == y2 || x1 == x2 && y1 != y2)
-                    .collect()
-            } else {
-                commands
-            }
-        };
+    fn draw(commands: &CommandList) -> Result<Bitmap> {
         let (max_x, max_y) = commands.required_bounds();
-
         let mut bitmap = Bitmap::new(max_x, max_y);
         commands.apply_commands(&mut bitmap)?;
+        Ok(bitmap)
+    }
+
+    fn overlap_count(commands: &CommandList) -> Result<Answer> {
+        let bitmap = Self::draw(commands)?;
+        Ok(bitmap.iter().filter(|x| *x > 1).count().into())
+    }
+}
 
-        let overlap_count = bitmap.iter().filter(|x| *x > 1).count();
+impl Solution for Day5 {
+    type Parsed = CommandList;
 
-        println!("# points where lines overlap: {}", overlap_count);
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        let input = read_input(input)?;
+        Ok(CommandList::parse(input.as_str())?)
+    }
 
+    fn part1(&self, commands: &Self::Parsed) -> Result<Answer> {
+        let commands = commands
+            .iter()
+            .filter(|((x1, y1), (x2, y2))| x1 == x2 || y1 == y2)
+            .cloned()
+            .collect();
+        Self::overlap_count(&commands)
+    }
+
+    fn part2(&self, commands: &Self::Parsed) -> Result<Answer> {
+        Self::overlap_count(commands)
+    }
+
+    fn extra_output(&self, commands: &Self::Parsed, out: &mut dyn Write) -> Result<()> {
         if self.print_diagram {
-            println!("{}", bitmap);
+            writeln!(out, "{}", Self::draw(commands)?)?;
         }
-
         Ok(())
     }
 }
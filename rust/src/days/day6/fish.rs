@@ -5,12 +5,14 @@ use std::io::BufRead;
 use anyhow::Result;
 use itertools::Itertools;
 
+use crate::parse::ParseError;
+
 const DEFAULT_TIMER: usize = 6;
 const NEW_FISH_DELAY: usize = 2;
 const ARRAY_SIZE: usize = DEFAULT_TIMER + NEW_FISH_DELAY + 1;
 
-#[derive(Debug, PartialEq)]
-pub(crate) struct Fish {
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fish {
     buf: [u128; ARRAY_SIZE],
 }
 
@@ -19,15 +21,26 @@ impl Fish {
         Fish { buf }
     }
 
-    pub(crate) fn parse(mut reader: impl BufRead) -> Result<Self> {
+    pub fn parse(mut reader: impl BufRead) -> Result<Self> {
         let mut buf = String::new();
         reader.read_line(&mut buf)?;
 
+        let line = buf.trim_end();
+        let mut timers = Vec::new();
+        let mut offset = 0;
+        for src in line.split(',') {
+            match src.parse::<usize>() {
+                Ok(timer) if timer < ARRAY_SIZE => timers.push(timer),
+                _ => {
+                    let expected = format!("a timer from 0 to {}", ARRAY_SIZE - 1);
+                    return Err(ParseError::at_offset(line.as_bytes(), offset, expected).into());
+                }
+            }
+            offset += src.len() + 1;
+        }
+
         let mut result = [0; ARRAY_SIZE];
-        buf.trim_end()
-            .split(',')
-            .map(|src| Ok(usize::from_str_radix(src, 10)?))
-            .collect::<Result<Vec<_>>>()?
+        timers
             .into_iter()
             .counts()
             .into_iter()
@@ -35,18 +48,25 @@ impl Fish {
         Ok(Self::new(result))
     }
 
-    pub(crate) fn count(&self) -> u128 {
-        self.buf.iter().sum()
+    /// The number of fish, or `None` if there are too many to count.
+    pub fn count(&self) -> Option<u128> {
+        self.buf
+            .iter()
+            .try_fold(0u128, |count, fish| count.checked_add(*fish))
     }
 
-    pub(crate) fn tick(&mut self) {
-        self.buf[DEFAULT_TIMER + 1] += self.buf[0];
+    /// Advances the fish by a day, or returns `None` (leaving them unchanged) if there would be
+    /// too many to count.
+    pub fn tick(&mut self) -> Option<()> {
+        let reset_fish = self.buf[DEFAULT_TIMER + 1].checked_add(self.buf[0])?;
+        self.buf[DEFAULT_TIMER + 1] = reset_fish;
         let birthed_fish = self.buf[0];
         let mut it = self.buf.iter_mut().peekable();
         while let (Some(elem), Some(next)) = (it.next(), it.peek()) {
             *elem = **next;
         }
         self.buf[DEFAULT_TIMER + NEW_FISH_DELAY] = birthed_fish;
+        Some(())
     }
 }
 
@@ -62,6 +82,13 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn read_input_rejects_timers_that_are_too_long() {
+        let expected_error = ParseError::new(1, 5, "3,4,9,1", "a timer from 0 to 8");
+        let error = Fish::parse("3,4,9,1\n".as_bytes()).unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
     #[test]
     fn tick_decreases_the_timer_of_the_fish_by_one() {
         let expected_fish = Fish::new([0, 0, 0, 0, 1, 0, 0, 0, 0]);
@@ -96,9 +123,18 @@ mod tests {
 
     #[test]
     fn count_returns_total_number_of_fish() {
-        let expected_count = 88;
+        let expected_count = Some(88);
         let fish = Fish::new([1, 1, 2, 3, 5, 8, 13, 21, 34]);
         let count = fish.count();
         assert_eq!(count, expected_count);
     }
+
+    #[test]
+    fn too_many_fish_to_count_is_not_a_count() {
+        let expected_fish = Fish::new([u128::MAX, 0, 0, 0, 0, 0, 0, 1, 0]);
+        let mut fish = expected_fish.clone();
+        assert_eq!(fish.tick(), None);
+        assert_eq!(fish, expected_fish);
+        assert_eq!(fish.count(), None);
+    }
 }
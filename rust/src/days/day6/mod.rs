@@ -1,31 +1,92 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::{fs::File, io::BufReader, path::PathBuf};
+use std::{fmt, io::BufRead};
 
 use anyhow::Result;
-use clap::Parser;
 
-use crate::days::day6::fish::Fish;
+use crate::solution::{Answer, Solution};
 
 mod fish;
 
-#[derive(Parser)]
-#[clap(about = "Lanternfish")]
-pub(crate) struct Day6 {
-    input: PathBuf,
-    #[clap(short, long)]
-    days: usize,
+pub use self::fish::Fish;
+
+/// Day 6: Lanternfish
+#[derive(Clone, Copy, Debug)]
+pub struct Day6 {
+    /// The number of days to simulate for part 1.
+    pub days: usize,
+    /// The number of days to simulate for part 2.
+    pub extended_days: usize,
+}
+
+impl Default for Day6 {
+    fn default() -> Self {
+        Day6 {
+            days: 80,
+            extended_days: 256,
+        }
+    }
+}
+
+/// There are too many fish to count after this many days.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Overflow {
+    pub days: usize,
 }
 
 impl Day6 {
-    pub(crate) fn run(self) -> Result<()> {
-        let file = File::open(self.input)?;
-        let reader = BufReader::new(file);
+    fn count_after(fish: &Fish, days: usize) -> Result<Answer> {
+        let mut fish = fish.clone();
+        for day in 1..=days {
+            fish.tick().ok_or(Overflow { days: day })?;
+        }
+        let count = fish.count().ok_or(Overflow { days })?;
+        Ok(Answer::from(count).with("days", days))
+    }
+}
+
+impl Solution for Day6 {
+    type Parsed = Fish;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        Fish::parse(input)
+    }
+
+    fn part1(&self, fish: &Self::Parsed) -> Result<Answer> {
+        Self::count_after(fish, self.days)
+    }
+
+    fn part2(&self, fish: &Self::Parsed) -> Result<Answer> {
+        Self::count_after(fish, self.extended_days)
+    }
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The fish count overflows after {} days", self.days)
+    }
+}
+
+impl std::error::Error for Overflow {}
 
-        let mut fish = Fish::parse(reader)?;
-        (0..self.days).for_each(|_| fish.tick());
-        println!("After {} days, there are {} fish.", self.days, fish.count());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn counts_that_overflow_are_reported_with_the_day() -> Result<()> {
+        // The fish only overflow once they are added up after 1,000 days, but much later, there
+        // are too many with the same timer.
+        let expected_errors = [Overflow { days: 1000 }, Overflow { days: 1021 }];
+        for (expected_error, extended_days) in expected_errors.into_iter().zip([1000, 2000]) {
+            let day6 = Day6 {
+                extended_days,
+                ..Day6::default()
+            };
+            let fish = day6.parse(&mut "3,4,3,1,2\n".as_bytes())?;
+            let error = day6.part2(&fish).unwrap_err();
+            assert_eq!(error.downcast_ref::<Overflow>(), Some(&expected_error));
+        }
         Ok(())
     }
 }
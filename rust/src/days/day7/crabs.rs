@@ -1,67 +1,81 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::cmp::Ordering;
+use std::fmt;
 
-use anyhow::anyhow;
 use nom::{
     bytes::complete::tag,
-    character::complete::digit1,
-    combinator::{map, map_res},
+    character::complete::{digit1, multispace0},
+    combinator::{cut, eof, map, map_res},
+    error::context,
     multi::separated_list1,
-    IResult,
+    sequence::{terminated, tuple},
 };
 
+use crate::parse::{IResult, ParseError};
+
+#[derive(Debug, PartialEq)]
+pub struct Positions(Vec<i32>);
+
+/// The fuel needed to move every crab to a position is too much to count.
 #[derive(Debug, PartialEq)]
-pub(crate) struct Positions(Vec<i32>);
+pub struct FuelOverflowError {
+    pub position: i64,
+}
 
 impl Positions {
-    pub(crate) fn lowest_cost_target_position(&self, crab_engineering: bool) -> (i32, i32) {
+    pub fn lowest_cost_target_position(
+        &self,
+        crab_engineering: bool,
+    ) -> Result<(i64, i64), FuelOverflowError> {
         if crab_engineering {
-            self.find_cheapest(
-                0,
-                self.calculate_candidates(|num, new_position| {
-                    let delta = (num - new_position).abs();
-                    (delta * delta + delta) / 2
-                }),
-            )
+            self.find_cheapest(|num, new_position| {
+                let delta = (num - new_position).abs();
+                (delta * delta + delta) / 2
+            })
         } else {
-            self.find_cheapest(
-                0,
-                self.calculate_candidates(|num, new_position| (num - new_position).abs()),
-            )
+            self.find_cheapest(|num, new_position| (num - new_position).abs())
         }
     }
 
-    fn calculate_candidates<'a>(
-        &'a self,
-        f: impl Fn(i32, i32) -> i32 + 'a,
-    ) -> impl Fn(i32) -> (i32, i32, i32) + 'a {
-        move |num: i32| {
-            (
-                self.cost_to_move(num - 1, &f),
-                self.cost_to_move(num, &f),
-                self.cost_to_move(num + 1, &f),
-            )
+    // The total cost is convex, so the cheapest position is the first one where moving one more
+    // position to the right stops saving fuel.  That also holds when several positions tie.
+    fn find_cheapest(&self, f: impl Fn(i64, i64) -> i64) -> Result<(i64, i64), FuelOverflowError> {
+        let cost = |position| self.cost_to_move(position, &f);
+        let (mut low, mut high) = self.0.iter().fold((i64::MAX, i64::MIN), |(low, high), x| {
+            (low.min(i64::from(*x)), high.max(i64::from(*x)))
+        });
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if cost(middle + 1)? < cost(middle)? {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
         }
+        Ok((low, cost(low)?))
     }
 
-    fn find_cheapest(&self, start: i32, f: impl Fn(i32) -> (i32, i32, i32)) -> (i32, i32) {
-        let (left, current, right) = f(start);
-        match (left.cmp(&current), right.cmp(&current)) {
-            (Ordering::Greater, Ordering::Greater) => (start, current),
-            (Ordering::Less, _) => self.find_cheapest(start - 1, f),
-            (_, Ordering::Less) => self.find_cheapest(start + 1, f),
-            _ => panic!("The problem fails to converge, which is bad for Santa.  ☠️ 🎅"),
-        }
+    fn cost_to_move(
+        &self,
+        new_position: i64,
+        f: impl Fn(i64, i64) -> i64,
+    ) -> Result<i64, FuelOverflowError> {
+        self.0
+            .iter()
+            .try_fold(0i64, |sum, x| {
+                sum.checked_add(f(i64::from(*x), new_position))
+            })
+            .ok_or(FuelOverflowError {
+                position: new_position,
+            })
     }
 
-    fn cost_to_move(&self, new_position: i32, f: impl Fn(i32, i32) -> i32) -> i32 {
-        self.0.iter().fold(0, |sum, x| sum + f(*x, new_position))
-    }
-
-    pub(crate) fn parse(input: &str) -> IResult<&str, Positions> {
-        let number = map_res(digit1, |src| i32::from_str_radix(src, 10));
-        let numbers = separated_list1(tag(","), number);
+    pub fn parse(input: &str) -> IResult<&str, Positions> {
+        let number = context(
+            "a position",
+            map_res(digit1, |src: &str| src.parse::<i32>()),
+        );
+        let numbers = separated_list1(tag(","), cut(number));
         map(numbers, Positions::from)(input)
     }
 }
@@ -76,11 +90,25 @@ impl TryFrom<&str> for Positions {
     type Error = anyhow::Error;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
-        let (_, result) = Self::parse(input).map_err(|err| anyhow!("{}", err))?;
+        let end = context("‘,’ or the end of the input", tuple((multispace0, eof)));
+        let (_, result) =
+            terminated(Self::parse, end)(input).map_err(|err| ParseError::from_nom(input, err))?;
         Ok(result)
     }
 }
 
+impl fmt::Display for FuelOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Moving the crabs to position {} takes more fuel than can be counted",
+            self.position
+        )
+    }
+}
+
+impl std::error::Error for FuelOverflowError {}
+
 impl From<Vec<i32>> for Positions {
     fn from(value: Vec<i32>) -> Self {
         Positions(value)
@@ -99,26 +127,53 @@ mod tests {
     }
 
     #[test]
-    fn solver_finds_the_cheapest_position() {
+    fn parser_reports_where_the_input_is_invalid() {
+        let expected_error = ParseError::new(1, 5, "16,1;2", "‘,’ or the end of the input");
+        let error = Positions::try_from("16,1;2\n").unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>(), Some(&expected_error));
+    }
+
+    #[test]
+    fn solver_finds_the_cheapest_position() -> Result<(), FuelOverflowError> {
         let expected_position = 2;
         let positions = Positions::from([16, 1, 2, 0, 4, 2, 7, 1, 2, 14]);
-        let (position, _) = positions.lowest_cost_target_position(false);
+        let (position, _) = positions.lowest_cost_target_position(false)?;
         assert_eq!(position, expected_position);
+        Ok(())
     }
 
     #[test]
-    fn solver_calculates_the_fuel_cost_to_move() {
+    fn solver_calculates_the_fuel_cost_to_move() -> Result<(), FuelOverflowError> {
         let expected_cost = 37;
         let positions = Positions::from([16, 1, 2, 0, 4, 2, 7, 1, 2, 14]);
-        let (_, cost) = positions.lowest_cost_target_position(false);
+        let (_, cost) = positions.lowest_cost_target_position(false)?;
         assert_eq!(cost, expected_cost);
+        Ok(())
     }
 
     #[test]
     fn solver_supports_crab_engineering() {
-        let expected_result = (5, 168);
+        let expected_result = Ok((5, 168));
         let positions = Positions::from([16, 1, 2, 0, 4, 2, 7, 1, 2, 14]);
         let result = positions.lowest_cost_target_position(true);
         assert_eq!(result, expected_result);
     }
+
+    #[test]
+    fn solver_stops_on_a_plateau() {
+        let expected_result = Ok((0, 2));
+        let positions = Positions::from([0, 2]);
+        let result = positions.lowest_cost_target_position(false);
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn solver_reports_fuel_costs_that_overflow() {
+        let expected_error = Err(FuelOverflowError {
+            position: 1073741824,
+        });
+        let positions = Positions::from([[0; 20], [i32::MAX; 20]].concat());
+        let result = positions.lowest_cost_target_position(true);
+        assert_eq!(result, expected_error);
+    }
 }
@@ -2,30 +2,41 @@
 
 mod crabs;
 
-use std::path::PathBuf;
+use std::io::BufRead;
 
 use anyhow::Result;
-use clap::Parser;
 
-use crate::util::read_input;
+use crate::{
+    solution::{Answer, Solution},
+    util::read_input,
+};
 
-use self::crabs::Positions;
+pub use self::crabs::{FuelOverflowError, Positions};
 
-#[derive(Parser)]
-#[clap(about = "The Treachery of Whales")]
-pub(crate) struct Day7 {
-    input: PathBuf,
-    #[clap(short, long)]
-    crab_engineering: bool,
-}
+/// Day 7: The Treachery of Whales
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day7;
 
 impl Day7 {
-    pub(crate) fn run(self) -> Result<()> {
-        let input = read_input(self.input)?;
-        let positions = Positions::try_from(input.as_str())?;
-        let (new_position, fuel_cost) =
-            positions.lowest_cost_target_position(self.crab_engineering);
-        println!("Fuel cost for move to {}: {}", new_position, fuel_cost);
-        Ok(())
+    fn fuel_cost(positions: &Positions, crab_engineering: bool) -> Result<Answer> {
+        let (new_position, fuel_cost) = positions.lowest_cost_target_position(crab_engineering)?;
+        Ok(Answer::from(fuel_cost).with("position", new_position))
+    }
+}
+
+impl Solution for Day7 {
+    type Parsed = Positions;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        let input = read_input(input)?;
+        Positions::try_from(input.as_str())
+    }
+
+    fn part1(&self, positions: &Self::Parsed) -> Result<Answer> {
+        Self::fuel_cost(positions, false)
+    }
+
+    fn part2(&self, positions: &Self::Parsed) -> Result<Answer> {
+        Self::fuel_cost(positions, true)
     }
 }
@@ -2,17 +2,20 @@
 
 use std::{collections::HashMap, ops::BitOr};
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use itertools::Itertools;
 use nom::{
     bytes::complete::{tag, take_while_m_n},
-    character::complete::space1,
-    combinator::map_res,
-    sequence::{preceded, separated_pair, tuple},
-    IResult,
+    character::complete::{multispace0, space1},
+    combinator::{eof, map_res},
+    error::context,
+    sequence::{preceded, separated_pair, terminated, tuple},
+    Offset,
 };
 use phf::phf_map;
 
+use crate::parse::{IResult, ParseError};
+
 static SEGMENT_MAPPING: phf::Map<u8, u8> = phf_map! {
     b'a' => 0b0000001,
     b'b' => 0b0000010,
@@ -31,7 +34,7 @@ fn to_repr(segments: &[u8]) -> u8 {
 }
 
 #[derive(Debug, PartialEq)]
-pub(crate) struct Display {
+pub struct Display {
     output: [u8; 4],
 }
 
@@ -69,70 +72,104 @@ impl Display {
         }
 
         let segments = take_while_m_n(2, 7, is_segment);
-        let digits = separated_list_n(10, space1, &segments);
-        let output = separated_list_n(4, space1, &segments);
+        let digits = context(
+            "ten patterns of 2 to 7 segments (a to g)",
+            separated_list_n(10, space1, &segments),
+        );
+        let output = context(
+            "four patterns of 2 to 7 segments (a to g)",
+            separated_list_n(4, space1, &segments),
+        );
+        let separator = tuple((space1, context("‘|’", tag(b"|")), space1));
+        let end = context("the end of the line", tuple((multispace0, eof)));
         let mut line = map_res(
-            separated_pair(digits, tuple((space1, tag(b"|"), space1)), output),
+            terminated(separated_pair(digits, separator, output), end),
             from_vec,
         );
         line(input)
     }
 
-    pub(crate) fn parse(input: &str) -> Result<Display> {
+    pub fn parse(input: &str) -> Result<Display, ParseError> {
+        let source = input.as_bytes();
         let (_, (digits, output)) =
-            Self::parser(input.as_bytes()).map_err(|err| anyhow!("{}", err))?;
-        Ok(Display::decode(digits, output))
+            Self::parser(source).map_err(|err| ParseError::from_nom(source, err))?;
+        Display::decode(source, digits, output)
     }
 
-    fn decode(digits: [&[u8]; 10], output: [&[u8]; 4]) -> Display {
-        let mapping: HashMap<_, _> = digits
-            .into_iter()
-            .sorted_by_key(|key| key.len())
-            .fold(
-                HashMap::<u8, u8>::with_capacity(10),
-                |mut mapping, segments| {
-                    let bit_repr = to_repr(segments);
-                    let number = match segments {
-                        x if x.len() == 2 => 1,
-                        x if x.len() == 5
-                            && ((bit_repr | 0b10000000) & !mapping[&4]) == !mapping[&4] =>
-                        {
-                            2
-                        }
-                        x if x.len() == 5 && (bit_repr & mapping[&1]) == mapping[&1] => 3,
-                        x if x.len() == 4 => 4,
-                        x if x.len() == 5
-                            && (bit_repr & (mapping[&4] ^ mapping[&7]))
-                                == (mapping[&4] ^ mapping[&7]) =>
-                        {
-                            5
-                        }
-                        x if x.len() == 6
-                            && ((bit_repr | 0b10000000) & !mapping[&7]) == !mapping[&7] =>
-                        {
-                            6
-                        }
-                        x if x.len() == 3 => 7,
-                        x if x.len() == 7 => 8,
-                        x if x.len() == 6 && (bit_repr & mapping[&4]) == mapping[&4] => 9,
-                        _ => 0,
-                    };
-                    mapping.insert(number, bit_repr);
-                    mapping
-                },
-            )
-            .into_iter()
-            .map(|(k, v)| (v, k))
-            .collect();
-        Display {
-            output: {
-                let mut mapped_output = [0; 4];
-                for (index, number) in output.into_iter().map(to_repr).enumerate() {
-                    mapped_output[index] = mapping[&number];
+    fn decode(
+        source: &[u8],
+        digits: [&[u8]; 10],
+        output: [&[u8]; 4],
+    ) -> Result<Display, ParseError> {
+        // Every other digit is found by comparing it with these, so they have to be there.
+        for (length, digit) in [(2, 1), (3, 7), (4, 4)] {
+            if digits.iter().all(|segments| segments.len() != length) {
+                let expected = format!("a pattern of {} segments for {}", length, digit);
+                return Err(ParseError::at_offset(source, 0, expected));
+            }
+        }
+        let mut patterns = HashMap::<u8, u8>::with_capacity(10);
+        let mut duplicate = None;
+        for segments in digits.into_iter().sorted_by_key(|key| key.len()) {
+            let bit_repr = to_repr(segments);
+            let number = match segments {
+                x if x.len() == 2 => 1,
+                x if x.len() == 5 && ((bit_repr | 0b10000000) & !patterns[&4]) == !patterns[&4] => {
+                    2
+                }
+                x if x.len() == 5 && (bit_repr & patterns[&1]) == patterns[&1] => 3,
+                x if x.len() == 4 => 4,
+                x if x.len() == 5
+                    && (bit_repr & (patterns[&4] ^ patterns[&7]))
+                        == (patterns[&4] ^ patterns[&7]) =>
+                {
+                    5
                 }
-                mapped_output
-            },
+                x if x.len() == 6 && ((bit_repr | 0b10000000) & !patterns[&7]) == !patterns[&7] => {
+                    6
+                }
+                x if x.len() == 3 => 7,
+                x if x.len() == 7 => 8,
+                x if x.len() == 6 && (bit_repr & patterns[&4]) == patterns[&4] => 9,
+                x if x.len() == 6 => 0,
+                _ => {
+                    let expected = "a pattern for a digit";
+                    return Err(ParseError::at_offset(
+                        source,
+                        source.offset(segments),
+                        expected,
+                    ));
+                }
+            };
+            if patterns.insert(number, bit_repr).is_some() && duplicate.is_none() {
+                duplicate = Some((number, segments));
+            }
         }
+        // Ten patterns for ten digits means another digit is missing whenever one is repeated.
+        if let Some((number, segments)) = duplicate {
+            let missing = (0..10).find(|digit| !patterns.contains_key(digit));
+            let expected = format!(
+                "a pattern for {} instead of another {}",
+                missing.unwrap_or_default(),
+                number
+            );
+            return Err(ParseError::at_offset(
+                source,
+                source.offset(segments),
+                expected,
+            ));
+        }
+        let mapping: HashMap<_, _> = patterns.into_iter().map(|(k, v)| (v, k)).collect();
+        let mut mapped_output = [0; 4];
+        for (index, segments) in output.into_iter().enumerate() {
+            mapped_output[index] = *mapping.get(&to_repr(segments)).ok_or_else(|| {
+                let expected = "one of the patterns before ‘|’";
+                ParseError::at_offset(source, source.offset(segments), expected)
+            })?;
+        }
+        Ok(Display {
+            output: mapped_output,
+        })
     }
 }
 
@@ -142,12 +179,12 @@ impl std::fmt::Display for Display {
     }
 }
 
-impl From<Display> for u32 {
-    fn from(display: Display) -> Self {
+impl From<&Display> for u32 {
+    fn from(display: &Display) -> Self {
         display
             .output
-            .into_iter()
-            .fold(0, |acc, digit| acc * 10 + digit as u32)
+            .iter()
+            .fold(0, |acc, digit| acc * 10 + *digit as u32)
     }
 }
 
@@ -275,6 +312,86 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parser_reports_where_the_input_is_invalid() {
+        let expected_error = ParseError::new(
+            1,
+            65,
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | ab ax ab ab",
+            "four patterns of 2 to 7 segments (a to g)",
+        );
+        let input = concat!(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab ",
+            "| ab ax ab ab"
+        );
+        let error = Display::parse(input).unwrap_err();
+        assert_eq!(error, expected_error);
+    }
+
+    #[test]
+    fn decode_reports_a_missing_pattern() {
+        let expected_error = ParseError::new(
+            1,
+            1,
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb abc | ab ab ab ab",
+            "a pattern of 2 segments for 1",
+        );
+        let input = concat!(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb abc ",
+            "| ab ab ab ab"
+        );
+        let error = Display::parse(input).unwrap_err();
+        assert_eq!(error, expected_error);
+    }
+
+    #[test]
+    fn decode_reports_a_digit_with_two_patterns() {
+        let expected_error = ParseError::new(
+            1,
+            21,
+            "acedgfb abcde gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | ab ab ab ab",
+            "a pattern for 5 instead of another 3",
+        );
+        let input = concat!(
+            "acedgfb abcde gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab ",
+            "| ab ab ab ab"
+        );
+        let error = Display::parse(input).unwrap_err();
+        assert_eq!(error, expected_error);
+    }
+
+    #[test]
+    fn decode_reports_a_pattern_that_is_no_digit() {
+        let expected_error = ParseError::new(
+            1,
+            9,
+            "acedgfb acefg gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | ab ab ab ab",
+            "a pattern for a digit",
+        );
+        let input = concat!(
+            "acedgfb acefg gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab ",
+            "| ab ab ab ab"
+        );
+        let error = Display::parse(input).unwrap_err();
+        assert_eq!(error, expected_error);
+    }
+
+    #[test]
+    fn decode_reports_output_that_matches_no_pattern() {
+        let expected_error = ParseError::new(
+            1,
+            65,
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | ab ac ab ab",
+            "one of the patterns before ‘|’",
+        );
+        let input = concat!(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab ",
+            "| ab ac ab ab"
+        );
+        let error = Display::parse(input).unwrap_err();
+        assert_eq!(error, expected_error);
+    }
+
     #[test]
     fn it_converts_into_u32() -> Result<()> {
         let expected_number = 9999;
@@ -283,7 +400,7 @@ mod tests {
             "| cefabd cefabd cefabd cefabd"
         );
         let display = Display::parse(input)?;
-        assert_eq!(u32::from(display), expected_number);
+        assert_eq!(u32::from(&display), expected_number);
         Ok(())
     }
 }
@@ -2,31 +2,37 @@
 
 mod display;
 
-use std::path::PathBuf;
+use std::io::BufRead;
 
 use anyhow::Result;
-use clap::Parser;
 use itertools::Itertools;
 
-use crate::{days::day8::display::Display, util::read_input};
+use crate::{
+    solution::{Answer, Solution},
+    util::read_input,
+};
 
-#[derive(Parser)]
-#[clap(about = "Seven Segment Search")]
-pub(crate) struct Day8 {
-    input: PathBuf,
-}
+pub use self::display::Display;
 
-impl Day8 {
-    pub(crate) fn run(self) -> Result<()> {
-        let input = read_input(self.input)?;
-        let displays = Self::parse_displays(input.as_str())?;
+/// Day 8: Seven Segment Search
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = Vec<Display>;
 
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        let input = read_input(input)?;
+        Self::parse_displays(input.as_str())
+    }
+
+    fn part1(&self, displays: &Self::Parsed) -> Result<Answer> {
         let digit_counts: usize = displays
             .iter()
             .map(|display| -> usize {
                 format!("{}", display)
                     .as_bytes()
-                    .into_iter()
+                    .iter()
                     .counts()
                     .into_iter()
                     .filter_map(|(digit, count)| {
@@ -39,19 +45,22 @@ impl Day8 {
                     .sum()
             })
             .sum();
-        println!("The count of digits [1, 4, 7, 8] on all the displays: {}", digit_counts);
-
-        let total: u32 = displays
-            .into_iter()
-            .map(u32::from)
-            .sum();
-        println!("The total of the displays is: {}", total);
+        Ok(digit_counts.into())
+    }
 
-        Ok(())
+    fn part2(&self, displays: &Self::Parsed) -> Result<Answer> {
+        let total: u32 = displays.iter().map(u32::from).sum();
+        Ok(total.into())
     }
+}
 
-    fn parse_displays<'a>(input: &'a str) -> Result<Vec<Display>> {
-        input.lines().map(Display::parse).collect()
+impl Day8 {
+    fn parse_displays(input: &str) -> Result<Vec<Display>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| Ok(Display::parse(line).map_err(|err| err.on_line(idx + 1))?))
+            .collect()
     }
 }
 
@@ -2,63 +2,64 @@
 
 use std::collections::HashSet;
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use nom::{
-    branch::alt,
-    character::complete::{digit1, line_ending, one_of},
-    combinator::{eof, map, map_opt},
-    multi::{many_m_n, separated_list1},
-    sequence::{preceded, terminated},
-    IResult,
+    character::complete::{digit1, one_of},
+    combinator::{map, map_opt},
+    error::context,
+    multi::many_m_n,
 };
 
+use crate::parse::{lines_of, IResult, ParseError};
+
 #[derive(Debug, PartialEq)]
-pub(crate) struct Heightmap {
+pub struct Heightmap {
     grid: Vec<Vec<u32>>,
     width: usize,
     height: usize,
 }
 
-pub(crate) struct Neighbors {
-    pub(crate) top: Option<u32>,
-    pub(crate) bottom: Option<u32>,
-    pub(crate) left: Option<u32>,
-    pub(crate) right: Option<u32>,
+pub struct Neighbors {
+    pub top: Option<u32>,
+    pub bottom: Option<u32>,
+    pub left: Option<u32>,
+    pub right: Option<u32>,
 }
 
 #[derive(Debug, Hash, Eq, PartialEq)]
-pub(crate) struct Point {
-    pub(crate) row: usize,
-    pub(crate) column: usize,
-    pub(crate) value: u32,
+pub struct Point {
+    pub row: usize,
+    pub column: usize,
+    pub value: u32,
 }
 
 impl Heightmap {
     fn heightmap(input: &str) -> IResult<&str, Heightmap> {
-        let (_, first_row) = digit1(input)?;
+        let (_, first_row) = context("a row of heights", digit1)(input)?;
         let width = first_row.len();
-        let row = many_m_n(
-            width,
-            width,
-            map_opt(one_of("0123456789"), |ch| ch.to_digit(10)),
-        );
-        let mut grid = terminated(
-            map(separated_list1(line_ending, row), |grid| Heightmap {
-                height: *&grid.len(),
-                grid,
+        let row = context(
+            "a row of heights as long as the first",
+            many_m_n(
                 width,
-            }),
-            alt((eof, preceded(line_ending, eof))),
+                width,
+                map_opt(one_of("0123456789"), |ch| ch.to_digit(10)),
+            ),
         );
+        let mut grid = map(lines_of(row), |grid| Heightmap {
+            height: grid.len(),
+            grid,
+            width,
+        });
         grid(input)
     }
 
-    pub(crate) fn parse(input: &str) -> Result<Heightmap> {
-        let (_, heightmap) = Self::heightmap(input).map_err(|err| anyhow!("{}", err))?;
+    pub fn parse(input: &str) -> Result<Heightmap, ParseError> {
+        let (_, heightmap) =
+            Self::heightmap(input).map_err(|err| ParseError::from_nom(input, err))?;
         Ok(heightmap)
     }
 
-    pub(crate) fn filter<'a, 'b>(
+    pub fn filter<'a, 'b>(
         &'a self,
         f: impl Fn(u32, Neighbors) -> bool + 'b,
     ) -> impl Iterator<Item = Point> + 'a
@@ -141,7 +142,7 @@ impl Heightmap {
         })
     }
 
-    pub(crate) fn map_basin(&self, row: usize, column: usize) -> HashSet<Point> {
+    pub fn map_basin(&self, row: usize, column: usize) -> HashSet<Point> {
         self.map_basin_impl(
             &Point {
                 row,
@@ -171,6 +172,13 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_reports_rows_that_are_too_short() {
+        let expected_error = ParseError::new(2, 4, "678", "a row of heights as long as the first");
+        let error = Heightmap::parse("12345\n678\n").unwrap_err();
+        assert_eq!(error, expected_error);
+    }
+
     #[test]
     fn filter_applies_the_filter() -> Result<()> {
         let expected_output = vec![Point {
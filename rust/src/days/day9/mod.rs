@@ -1,53 +1,58 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::path::PathBuf;
+use std::io::BufRead;
 
 mod heightmap;
 
 use anyhow::Result;
-use clap::Parser;
 use itertools::Itertools;
 
-use crate::util::read_input;
+use crate::{
+    solution::{Answer, Solution},
+    util::read_input,
+};
 
-use self::heightmap::Heightmap;
+pub use self::heightmap::{Heightmap, Neighbors, Point};
 
-#[derive(Parser)]
-#[clap(about = "Smoke Basin")]
-pub(crate) struct Day9 {
-    input: PathBuf,
-}
+/// Day 9: Smoke Basin
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day9;
 
 impl Day9 {
-    pub(crate) fn run(self) -> Result<()> {
-        let input = read_input(self.input)?;
-        let heightmap = Heightmap::parse(input.as_str())?;
-
-        let low_points: Vec<_> = heightmap
-            .filter(|value, neighbors| {
-                [
-                    neighbors.top.unwrap_or(0xA),
-                    neighbors.bottom.unwrap_or(0xA),
-                    neighbors.left.unwrap_or(0xA),
-                    neighbors.right.unwrap_or(0xA),
-                ]
-                .into_iter()
-                .all(|neighbor| value < neighbor)
-            })
-            .collect();
-
-        let risk_levels = low_points.iter().map(|pt| pt.value + 1);
-        println!("The sum of the risk levels: {}", risk_levels.sum::<u32>());
-
-        let basins: usize = low_points
-            .iter()
+    fn low_points(heightmap: &Heightmap) -> impl Iterator<Item = Point> + '_ {
+        heightmap.filter(|value, neighbors| {
+            [
+                neighbors.top.unwrap_or(0xA),
+                neighbors.bottom.unwrap_or(0xA),
+                neighbors.left.unwrap_or(0xA),
+                neighbors.right.unwrap_or(0xA),
+            ]
+            .into_iter()
+            .all(|neighbor| value < neighbor)
+        })
+    }
+}
+
+impl Solution for Day9 {
+    type Parsed = Heightmap;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed> {
+        let input = read_input(input)?;
+        Ok(Heightmap::parse(input.as_str())?)
+    }
+
+    fn part1(&self, heightmap: &Self::Parsed) -> Result<Answer> {
+        let risk_levels = Self::low_points(heightmap).map(|pt| pt.value + 1);
+        Ok(risk_levels.sum::<u32>().into())
+    }
+
+    fn part2(&self, heightmap: &Self::Parsed) -> Result<Answer> {
+        let basins: usize = Self::low_points(heightmap)
             .map(|pt| heightmap.map_basin(pt.row, pt.column).len())
             .sorted_unstable()
             .rev()
             .take(3)
             .product();
-        println!("The product of the three largest basins: {}", basins);
-
-        Ok(())
+        Ok(basins.into())
     }
 }
@@ -1,16 +1,12 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::declare_days;
-
-declare_days! [
-    Day1,
-    Day2,
-    Day3,
-    Day4,
-    Day5,
-    Day6,
-    Day7,
-    Day8,
-    Day9,
-    Day15,
-];
+pub mod day1;
+pub mod day15;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::io::{self, Write};
+
+use advent_2021::parse::ParseError;
+
+use crate::submit::{Outcome, Rejected};
+
+// The codes for bad input, I/O errors, and answers submitted too soon come from `sysexits.h`.
+// Clap already exits with 2 when the arguments are wrong.
+pub(crate) const FAILURE: i32 = 1;
+pub(crate) const BAD_INPUT: i32 = 65;
+pub(crate) const IO_ERROR: i32 = 74;
+pub(crate) const TRY_AGAIN: i32 = 75;
+
+/// The exit code for `error`.  An input that can’t be parsed is bad input even if the parse error
+/// was wrapped in other errors, and any other error caused by an I/O error is an I/O error.  An
+/// answer that wasn’t accepted only needs trying again later when it was submitted too soon.
+pub(crate) fn exit_code(error: &anyhow::Error) -> i32 {
+    let rejected = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<Rejected>());
+    if let Some(rejected) = rejected {
+        match rejected.outcome {
+            Outcome::RateLimited => TRY_AGAIN,
+            _ => FAILURE,
+        }
+    } else if error.chain().any(|cause| cause.is::<ParseError>()) {
+        BAD_INPUT
+    } else if error.chain().any(|cause| cause.is::<io::Error>()) {
+        IO_ERROR
+    } else {
+        FAILURE
+    }
+}
+
+/// Writes `error` followed by everything that caused it.  When `verbose` is set, the backtrace of
+/// where it happened is also written if one was captured.
+pub(crate) fn report(out: &mut dyn Write, error: &anyhow::Error, verbose: bool) -> io::Result<()> {
+    if verbose {
+        writeln!(
+            out,
+            "Something went wrong while solving the problem: {:?}",
+            error
+        )
+    } else {
+        writeln!(
+            out,
+            "Something went wrong while solving the problem: {}",
+            error
+        )?;
+        for cause in error.chain().skip(1) {
+            writeln!(out, "  caused by: {}", cause)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_2021::{days::day1::Day1, solution::Solution};
+    use anyhow::{anyhow, Result};
+
+    use super::*;
+
+    #[test]
+    fn errors_are_classified_by_their_causes() {
+        let bad_input = Day1::default().parse(&mut "20o\n".as_bytes()).unwrap_err();
+        let missing = io::Error::new(io::ErrorKind::NotFound, "no such file");
+        assert_eq!(exit_code(&bad_input.context("day1")), BAD_INPUT);
+        assert_eq!(exit_code(&anyhow!(missing).context("day5")), IO_ERROR);
+        assert_eq!(exit_code(&anyhow!("2 of 10 days failed")), FAILURE);
+        let too_soon = Rejected::new(Outcome::RateLimited, "too soon".into());
+        assert_eq!(exit_code(&too_soon.into()), TRY_AGAIN);
+        let too_high = Rejected::new(Outcome::TooHigh, "too high".into());
+        assert_eq!(exit_code(&too_high.into()), FAILURE);
+    }
+
+    #[test]
+    fn every_cause_is_reported() -> Result<()> {
+        let expected_output = concat!(
+            "Something went wrong while solving the problem: reading the input\n",
+            "  caused by: no such file\n",
+        );
+        let missing = io::Error::new(io::ErrorKind::NotFound, "no such file");
+        let error = anyhow!(missing).context("reading the input");
+        let mut buf = Vec::new();
+        report(&mut buf, &error, false)?;
+        assert_eq!(String::from_utf8(buf)?, expected_output);
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+use anyhow::{ensure, Result};
+use clap::Parser;
+
+use crate::{
+    client::{Client, ClientOptions},
+    paths,
+};
+
+#[derive(Parser)]
+#[clap(about = "Download a day’s puzzle input into the input cache")]
+pub(crate) struct Fetch {
+    /// The day to download (e.g., `5` or `day5`)
+    #[clap(parse(try_from_str = parse_day))]
+    day: u8,
+    #[clap(flatten)]
+    client: ClientOptions,
+}
+
+pub(crate) fn parse_day(src: &str) -> Result<u8> {
+    let day = src.trim_start_matches("day").parse()?;
+    ensure!(
+        (1..=25).contains(&day),
+        "Days run from 1 to 25, not {}",
+        day
+    );
+    Ok(day)
+}
+
+impl Fetch {
+    pub(crate) fn run(self) -> Result<()> {
+        let cache_dir = paths::cache_dir()?;
+        let input = paths::cached_input(&cache_dir, &format!("day{}", self.day));
+        if input.is_file() {
+            println!("Using the cached input at {}.", input.display());
+            Ok(())
+        } else {
+            let client = Client::new(self.client, cache_dir)?;
+            Self::download(&client, self.day, input)
+        }
+    }
+
+    fn download(client: &Client, day: u8, input: PathBuf) -> Result<()> {
+        let contents = client.get(&client.day_url(day, "/input"))?;
+        if let Some(parent) = input.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Write next to the input and rename it into place, so an interrupted download never leaves
+        // a truncated input in the cache.
+        let partial = input.with_extension("part");
+        fs::write(&partial, contents)?;
+        fs::rename(&partial, &input)?;
+        println!("Saved the input for day {} to {}.", day, input.display());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::{stub_client, stub_server};
+
+    #[test]
+    fn days_can_be_given_by_number_or_name() -> Result<()> {
+        assert_eq!(parse_day("5")?, 5);
+        assert_eq!(parse_day("day15")?, 15);
+        Ok(())
+    }
+
+    #[test]
+    fn days_outside_of_advent_are_rejected() {
+        assert!(parse_day("0").is_err());
+        assert!(parse_day("26").is_err());
+    }
+
+    #[test]
+    fn download_saves_the_input() -> Result<()> {
+        let cache = tempfile::tempdir()?;
+        let input = paths::cached_input(cache.path(), "day7");
+        let (base_url, server) = stub_server(200, "16,1,2,0,4,2,7,1,2,14\n");
+        let client = stub_client(base_url, cache.path().to_path_buf());
+        Fetch::download(&client, 7, input.clone())?;
+        server.join().unwrap();
+        assert_eq!(fs::read_to_string(&input)?, "16,1,2,0,4,2,7,1,2,14\n");
+        assert!(!input.with_extension("part").exists());
+        Ok(())
+    }
+}
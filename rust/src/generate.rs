@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::io::{stdout, Write};
+
+use anyhow::Result;
+use clap::Parser;
+
+use crate::fetch::parse_day;
+
+#[derive(Parser)]
+#[clap(about = "Generate a random input for a day’s puzzle")]
+pub(crate) struct Generate {
+    /// The day to generate an input for (e.g., `5` or `day5`)
+    #[clap(parse(try_from_str = parse_day))]
+    day: u8,
+    /// The same seed always generates the same input
+    #[clap(short, long, default_value = "0")]
+    seed: u64,
+    /// How big the input is: the number of lines or items, or the width of a grid
+    #[clap(short = 'n', long, default_value = "100")]
+    size: usize,
+}
+
+impl Generate {
+    pub(crate) fn run(self) -> Result<()> {
+        let input = advent_2021::random::input(self.day, self.seed, self.size)?;
+        stdout().write_all(input.as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    fs::File,
+    io::{stdin, BufRead, BufReader, Cursor, Read},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use flate2::bufread::MultiGzDecoder;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Opens a puzzle input for reading.  A path of `-` reads from stdin.  Inputs compressed with gzip
+/// or zstd are decompressed transparently based on their contents, so it also works for stdin.
+pub fn open(path: &Path) -> Result<Box<dyn BufRead>> {
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(BufReader::new(stdin()))
+    } else {
+        let file =
+            File::open(path).with_context(|| format!("Could not open {}", path.display()))?;
+        Box::new(BufReader::new(file))
+    };
+    decompress(reader)
+}
+
+fn decompress(mut reader: Box<dyn BufRead>) -> Result<Box<dyn BufRead>> {
+    // A pipe can hand over fewer bytes than the magic numbers at a time, so keep reading until
+    // there are enough to tell, then put them back in front of the rest.
+    let mut header = Vec::with_capacity(ZSTD_MAGIC.len());
+    reader
+        .by_ref()
+        .take(ZSTD_MAGIC.len() as u64)
+        .read_to_end(&mut header)?;
+    let is_gzip = header.starts_with(GZIP_MAGIC);
+    let is_zstd = header.starts_with(ZSTD_MAGIC);
+    let reader: Box<dyn BufRead> = Box::new(Cursor::new(header).chain(reader));
+    if is_gzip {
+        Ok(Box::new(BufReader::new(MultiGzDecoder::new(reader))))
+    } else if is_zstd {
+        let decoder = zstd::Decoder::with_buffer(reader)?;
+        Ok(Box::new(BufReader::new(decoder)))
+    } else {
+        Ok(reader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    const INPUT: &str = "3,4,3,1,2\n";
+
+    fn read_all(data: Vec<u8>) -> Result<String> {
+        let mut result = String::new();
+        decompress(Box::new(std::io::Cursor::new(data)))?.read_to_string(&mut result)?;
+        Ok(result)
+    }
+
+    #[test]
+    fn uncompressed_input_is_read_as_is() -> Result<()> {
+        let result = read_all(INPUT.as_bytes().to_vec())?;
+        assert_eq!(result, INPUT);
+        Ok(())
+    }
+
+    #[test]
+    fn gzip_input_is_decompressed() -> Result<()> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(INPUT.as_bytes())?;
+        let result = read_all(encoder.finish()?)?;
+        assert_eq!(result, INPUT);
+        Ok(())
+    }
+
+    #[test]
+    fn zstd_input_is_decompressed() -> Result<()> {
+        let compressed = zstd::encode_all(INPUT.as_bytes(), 0)?;
+        let result = read_all(compressed)?;
+        assert_eq!(result, INPUT);
+        Ok(())
+    }
+
+    /// Hands over its data one byte per read, like a slow pipe.
+    struct Trickle(Cursor<Vec<u8>>);
+
+    impl Read for Trickle {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = buf.len().min(1);
+            self.0.read(&mut buf[..len])
+        }
+    }
+
+    #[test]
+    fn input_read_a_byte_at_a_time_is_still_decompressed() -> Result<()> {
+        let compressed = zstd::encode_all(INPUT.as_bytes(), 0)?;
+        let reader = BufReader::new(Trickle(Cursor::new(compressed)));
+        let mut result = String::new();
+        decompress(Box::new(reader))?.read_to_string(&mut result)?;
+        assert_eq!(result, INPUT);
+        Ok(())
+    }
+
+    #[test]
+    fn empty_input_is_read_as_is() -> Result<()> {
+        let result = read_all(Vec::new())?;
+        assert_eq!(result, "");
+        Ok(())
+    }
+}
@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Solutions to Advent of Code 2021.  Each module in [`days`] has the types for that day’s puzzle
+//! and a solver implementing [`solution::Solution`].  The `advent_2021` binary is the command-line
+//! front end for them.
+
+pub mod days;
+pub mod input;
+pub mod parse;
+pub mod random;
+pub mod solution;
+mod util;
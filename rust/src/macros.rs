@@ -13,15 +13,89 @@ macro_rules! declare_days {
             #[derive(clap::Subcommand)]
             pub(crate) enum Command {
                 $(
-                    $x(crate::days::[<$x:lower>]::$x),
+                    $x(crate::commands::[<$x:lower>]::$x),
                 )*
             }
+
+            impl Command {
+                pub(crate) const NAMES: &'static [&'static str] = &[
+                    $(
+                        stringify!([<$x:lower>]),
+                    )*
+                ];
+
+                pub(crate) fn from_input(
+                    name: &str,
+                    input: Option<&std::path::Path>,
+                    config: &crate::config::Config,
+                ) -> anyhow::Result<Command> {
+                    use clap::{CommandFactory, FromArgMatches};
+                    // Without an input, the one from the config or the cache is used.
+                    let args = std::iter::once(std::ffi::OsStr::new(name))
+                        .chain(input.map(|input| input.as_os_str()));
+                    match name {
+                        $(
+                            stringify!([<$x:lower>]) => {
+                                let command = config.apply_to_day(
+                                    name,
+                                    crate::commands::[<$x:lower>]::$x::command(),
+                                );
+                                let matches = command.try_get_matches_from(args)?;
+                                Ok(Command::$x(
+                                    crate::commands::[<$x:lower>]::$x::from_arg_matches(&matches)?,
+                                ))
+                            }
+                        )*
+                        _ => Err(anyhow::anyhow!("Unknown day: {}", name)),
+                    }
+                }
+
+                pub(crate) fn name(&self) -> &'static str {
+                    match self {
+                        $(
+                            Command::$x(_) => stringify!([<$x:lower>]),
+                        )*
+                    }
+                }
+            }
         }
         impl Command {
-            pub(crate) fn run(self) -> anyhow::Result<()> {
+            pub(crate) fn solve(
+                &self,
+                parts: advent_2021::solution::Parts,
+                parallel: bool,
+            ) -> anyhow::Result<advent_2021::solution::Solved> {
+                let name = self.name();
+                match self {
+                    $(
+                        Command::$x(day) => {
+                            let input = crate::paths::input_or_cached(day.input.as_deref(), name)?;
+                            advent_2021::solution::solve(&day.solver(), &input, parts, parallel)
+                        }
+                    )*
+                }
+            }
+
+            pub(crate) fn run(
+                self,
+                format: crate::output::Format,
+                time: bool,
+                parts: advent_2021::solution::Parts,
+                watch: bool,
+            ) -> anyhow::Result<()> {
+                let name = self.name();
                 match self {
                     $(
-                        Command::$x(day) => day.run(),
+                        Command::$x(day) => {
+                            let input = crate::paths::input_or_cached(day.input.as_deref(), name)?;
+                            let solver = day.solver();
+                            if watch {
+                                crate::watch::watch(&solver, name, &input, format, time, parts)
+                            } else {
+                                crate::output::run(&solver, name, &input, format, time, parts, true)
+                                    .map(|_| ())
+                            }
+                        }
                     )*
                 }
             }
@@ -1,13 +1,48 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 mod app;
-mod days;
+mod bench;
+mod client;
+mod commands;
+mod completions;
+mod config;
+mod errors;
+mod fetch;
+mod generate;
 mod macros;
-mod util;
+mod manpage;
+mod output;
+mod paths;
+mod run_all;
+mod submit;
+mod verify;
+mod watch;
 
 fn main() {
-    let app = app::parse_options();
-    if let Err(error) = app.run() {
-        println!("Something went wrong while solving the problem: {}", error);
+    let config = std::env::current_dir()
+        .map_err(anyhow::Error::from)
+        .and_then(|dir| config::Config::find(&dir));
+    // A broken `advent.toml` only matters to the commands that read it, so `--help` and the like
+    // still work while it is being fixed.
+    let (config, config_error) = match config {
+        Ok(config) => (config, None),
+        Err(error) => (config::Config::default(), Some(error)),
+    };
+    let app = app::parse_options(&config);
+    let verbose = app.verbose();
+    if let Some(error) = config_error.filter(|_| app.uses_config()) {
+        fail(&error, verbose);
     }
+    if verbose {
+        // Errors only capture a backtrace when this is set before they are created.
+        std::env::set_var("RUST_LIB_BACKTRACE", "1");
+    }
+    if let Err(error) = app.run(&config) {
+        fail(&error, verbose);
+    }
+}
+
+fn fail(error: &anyhow::Error, verbose: bool) -> ! {
+    let _ = errors::report(&mut std::io::stderr(), error, verbose);
+    std::process::exit(errors::exit_code(error));
 }
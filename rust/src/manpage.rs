@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    io::{self, stdout, Write},
+    mem,
+};
+
+use anyhow::Result;
+use clap::{ArgAction, Command, CommandFactory, Parser};
+use clap_mangen::Man;
+
+use crate::app::App;
+
+#[derive(Parser)]
+#[clap(about = "Print the manual page in roff format (e.g., for `man -l -`)")]
+pub(crate) struct Manpage {}
+
+impl Manpage {
+    pub(crate) fn run(self) -> Result<()> {
+        write_manpage(&mut stdout().lock(), App::command())?;
+        Ok(())
+    }
+}
+
+/// Writes a manual page for `command` followed by the synopsis and options of every subcommand.
+fn write_manpage(out: &mut dyn Write, command: Command) -> io::Result<()> {
+    let command = plain_flags(command);
+    Man::new(command.clone()).render(out)?;
+    write_subcommands(out, command.get_name(), &command)
+}
+
+// `clap_mangen` documents a flag that sets `true` (so `advent.toml` can give it a default) as
+// taking a value, so they are turned back into plain flags first.
+fn plain_flags(mut command: Command) -> Command {
+    for subcommand in command.get_subcommands_mut() {
+        *subcommand = plain_flags(mem::take(subcommand));
+    }
+    let ids: Vec<_> = command
+        .get_arguments()
+        .filter(|arg| matches!(arg.get_action(), ArgAction::SetTrue))
+        .map(|arg| arg.get_id())
+        .collect();
+    ids.into_iter().fold(command, |command, id| {
+        command.mut_arg(id, |arg| {
+            arg.action(ArgAction::IncOccurrence)
+                .value_names(&[])
+                .takes_value(false)
+        })
+    })
+}
+
+// `clap_mangen` only lists the subcommands by name, so each one (and each of theirs) gets its own
+// section here with the sections `clap_mangen` would give it as subsections.
+fn write_subcommands(out: &mut dyn Write, path: &str, command: &Command) -> io::Result<()> {
+    for subcommand in command.get_subcommands() {
+        let path = format!("{} {}", path, subcommand.get_name());
+        writeln!(out, ".SH \"{}\"", path.to_uppercase())?;
+
+        let man = Man::new(subcommand.clone().name(&path));
+        let mut sections = Vec::new();
+        man.render_synopsis_section(&mut sections)?;
+        if subcommand.get_about().is_some() {
+            man.render_description_section(&mut sections)?;
+        }
+        man.render_options_section(&mut sections)?;
+        if subcommand.has_subcommands() {
+            man.render_subcommands_section(&mut sections)?;
+        }
+        for line in String::from_utf8_lossy(&sections).lines() {
+            match line.strip_prefix(".SH ") {
+                Some(heading) => writeln!(out, ".SS {}", heading)?,
+                // Every section defines the same string for quotes, which the page already has.
+                None if line.ends_with(".ds Aq \\(aq") || line.ends_with(".ds Aq '") => (),
+                None => writeln!(out, "{}", line)?,
+            }
+        }
+
+        write_subcommands(out, &path, subcommand)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn every_subcommand_and_its_options_are_documented() -> Result<()> {
+        let expected_lines = [
+            ".SH \"ADVENT_2021 DAY6\"",
+            "\\fBadvent_2021 day6\\fR [\\fB\\-h\\fR|\\fB\\-\\-help\\fR] [\\fB\\-d\\fR|\\fB\\-\\-days\\fR] [\\fB\\-e\\fR|\\fB\\-\\-extended\\-days\\fR] [\\fIINPUT\\fR] ",
+            "\\fB\\-d\\fR, \\fB\\-\\-days\\fR=\\fIDAYS\\fR [default: 80]",
+            "How many days to simulate for part 1",
+            "\\fB\\-p\\fR, \\fB\\-\\-print\\-diagram\\fR",
+            ".SH \"ADVENT_2021 BENCH DAY6\"",
+            ".SH \"ADVENT_2021 CONFIG SHOW\"",
+        ];
+        let mut buf = Vec::new();
+        write_manpage(&mut buf, App::command())?;
+        let manpage = String::from_utf8(buf)?;
+        for line in expected_lines {
+            assert!(manpage.lines().any(|l| l == line), "missing {:?}", line);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    io::{stderr, stdout, Write},
+    path::Path,
+};
+
+use advent_2021::solution::{solve_parsed, Answer, Parts, Solution, Solved, Timings};
+use anyhow::Result;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, clap::ArgEnum)]
+pub(crate) enum Format {
+    Plain,
+    Json,
+    Tsv,
+}
+
+#[derive(Serialize)]
+struct DayAnswers<'a> {
+    day: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<&'a Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<&'a Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timings: Option<&'a Timings>,
+}
+
+#[derive(Serialize)]
+struct DayError<'a> {
+    day: &'a str,
+    error: String,
+}
+
+impl Format {
+    /// Writes anything that needs to come before the first day’s answers.
+    pub(crate) fn write_header(self, out: &mut dyn Write) -> Result<()> {
+        if self == Format::Tsv {
+            writeln!(out, "day\tpart\tfield\tvalue")?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_answers(
+        self,
+        out: &mut dyn Write,
+        day: &str,
+        solved: &Solved,
+        time: bool,
+    ) -> Result<()> {
+        let timings = time.then(|| &solved.timings);
+        match self {
+            Format::Plain => {
+                for (part, answer) in solved.answers() {
+                    writeln!(out, "Part {}: {}", part, answer)?;
+                }
+                if let Some(timings) = timings {
+                    writeln!(out, "Time: {}", timings)?;
+                }
+            }
+            Format::Json => {
+                let answers = DayAnswers {
+                    day,
+                    part1: solved.part1.as_ref(),
+                    part2: solved.part2.as_ref(),
+                    timings,
+                };
+                serde_json::to_writer(&mut *out, &answers)?;
+                writeln!(out)?;
+            }
+            Format::Tsv => {
+                for (part, answer) in solved.answers() {
+                    writeln!(out, "{}\t{}\tanswer\t{}", day, part, answer.value)?;
+                    for (name, value) in &answer.details {
+                        writeln!(out, "{}\t{}\t{}\t{}", day, part, name, value)?;
+                    }
+                }
+                if let Some(timings) = timings {
+                    for (part, duration) in timings.phases() {
+                        let seconds = duration.as_secs_f64();
+                        writeln!(out, "{}\t{}\tseconds\t{}", day, part, seconds)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_error(
+        self,
+        out: &mut dyn Write,
+        day: &str,
+        error: &anyhow::Error,
+    ) -> Result<()> {
+        match self {
+            Format::Plain => writeln!(out, "{}: {}", day, error)?,
+            Format::Json => {
+                let error = error.to_string();
+                serde_json::to_writer(&mut *out, &DayError { day, error })?;
+                writeln!(out)?;
+            }
+            Format::Tsv => {
+                // Parse errors point at the problem over several lines, but each row needs to be
+                // a single line.  The first line has the location and what was expected.
+                let error = error.to_string();
+                let summary = error.lines().next().unwrap_or_default();
+                writeln!(out, "{}\t\terror\t{}", day, summary)?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Solves a day and prints its answers, followed by anything else the day was asked to output.
+/// The answers are preceded by the TSV header when `header` is set.  They are returned for anything
+/// that wants to do more with them.
+pub(crate) fn run<S: Solution>(
+    solution: &S,
+    day: &str,
+    input: &Path,
+    format: Format,
+    time: bool,
+    parts: Parts,
+    header: bool,
+) -> Result<Solved> {
+    let (parsed, solved) = solve_parsed(solution, input, parts, false)?;
+
+    let mut out = stdout();
+    if header {
+        format.write_header(&mut out)?;
+    }
+    format.write_answers(&mut out, day, &solved, time)?;
+
+    // Keep extra output out of the way of anything trying to parse the answers.
+    if format == Format::Plain {
+        solution.extra_output(&parsed, &mut out)?;
+    } else {
+        solution.extra_output(&parsed, &mut stderr())?;
+    }
+    Ok(solved)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn render(format: Format, time: bool) -> Result<String> {
+        render_parts(format, time, Parts::Both)
+    }
+
+    fn render_parts(format: Format, time: bool, parts: Parts) -> Result<String> {
+        let solved = Solved {
+            part1: parts
+                .includes(1)
+                .then(|| Answer::from(150).with("position", 15).with("depth", 10)),
+            part2: parts
+                .includes(2)
+                .then(|| Answer::from(900).with("position", 15).with("depth", 60)),
+            timings: Timings {
+                parse: Duration::from_millis(250),
+                part1: parts.includes(1).then(|| Duration::from_millis(500)),
+                part2: parts.includes(2).then(|| Duration::from_secs(2)),
+            },
+        };
+        let mut buf = Vec::new();
+        format.write_header(&mut buf)?;
+        format.write_answers(&mut buf, "day2", &solved, time)?;
+        Ok(String::from_utf8(buf)?)
+    }
+
+    #[test]
+    fn plain_output_lists_each_part() -> Result<()> {
+        let expected_output = concat!(
+            "Part 1: 150 (position: 15, depth: 10)\n",
+            "Part 2: 900 (position: 15, depth: 60)\n",
+        );
+        assert_eq!(render(Format::Plain, false)?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn json_output_is_one_object_per_day() -> Result<()> {
+        let expected_output = concat!(
+            r#"{"day":"day2","#,
+            r#""part1":{"answer":150,"position":15,"depth":10},"#,
+            r#""part2":{"answer":900,"position":15,"depth":60}}"#,
+            "\n",
+        );
+        assert_eq!(render(Format::Json, false)?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn tsv_output_is_one_row_per_field() -> Result<()> {
+        let expected_output = concat!(
+            "day\tpart\tfield\tvalue\n",
+            "day2\t1\tanswer\t150\n",
+            "day2\t1\tposition\t15\n",
+            "day2\t1\tdepth\t10\n",
+            "day2\t2\tanswer\t900\n",
+            "day2\t2\tposition\t15\n",
+            "day2\t2\tdepth\t60\n",
+        );
+        assert_eq!(render(Format::Tsv, false)?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn plain_output_includes_timings_when_requested() -> Result<()> {
+        let expected_output = concat!(
+            "Part 1: 150 (position: 15, depth: 10)\n",
+            "Part 2: 900 (position: 15, depth: 60)\n",
+            "Time: parse 250ms, part 1 500ms, part 2 2s\n",
+        );
+        assert_eq!(render(Format::Plain, true)?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn json_output_includes_timings_in_seconds_when_requested() -> Result<()> {
+        let expected_output = concat!(
+            r#"{"day":"day2","#,
+            r#""part1":{"answer":150,"position":15,"depth":10},"#,
+            r#""part2":{"answer":900,"position":15,"depth":60},"#,
+            r#""timings":{"parse":0.25,"part1":0.5,"part2":2.0}}"#,
+            "\n",
+        );
+        assert_eq!(render(Format::Json, true)?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn plain_output_lists_only_the_solved_parts() -> Result<()> {
+        let expected_output = concat!(
+            "Part 2: 900 (position: 15, depth: 60)\n",
+            "Time: parse 250ms, part 2 2s\n",
+        );
+        assert_eq!(
+            render_parts(Format::Plain, true, Parts::Part2)?,
+            expected_output
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn json_output_leaves_out_the_unsolved_parts() -> Result<()> {
+        let expected_output = concat!(
+            r#"{"day":"day2","#,
+            r#""part1":{"answer":150,"position":15,"depth":10},"#,
+            r#""timings":{"parse":0.25,"part1":0.5}}"#,
+            "\n",
+        );
+        assert_eq!(
+            render_parts(Format::Json, true, Parts::Part1)?,
+            expected_output
+        );
+        Ok(())
+    }
+}
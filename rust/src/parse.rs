@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use nom::{
+    branch::alt,
+    character::complete::line_ending,
+    combinator::{cut, eof, not},
+    error::{context, ErrorKind, VerboseError, VerboseErrorKind},
+    multi::many0,
+    sequence::{preceded, terminated},
+    AsBytes, Offset,
+};
+
+/// The result type used by the day parsers.  `VerboseError` keeps the context of each failure, so
+/// it can be turned into a useful `ParseError`.
+pub(crate) type IResult<I, O> = nom::IResult<I, O, VerboseError<I>>;
+
+/// An error in a puzzle input, with where it happened and what was expected there.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    file: Option<PathBuf>,
+    line: usize,
+    column: usize,
+    expected: String,
+    excerpt: String,
+}
+
+impl ParseError {
+    /// Creates an error at the given (one-based) line and column, where `excerpt` is that line.
+    pub(crate) fn new(
+        line: usize,
+        column: usize,
+        excerpt: &str,
+        expected: impl Into<String>,
+    ) -> ParseError {
+        ParseError {
+            file: None,
+            line,
+            column,
+            expected: expected.into(),
+            excerpt: excerpt.trim_end_matches(&['\r', '\n'][..]).to_string(),
+        }
+    }
+
+    /// Creates an error at the given byte offset into `source`.
+    pub(crate) fn at_offset(
+        source: &[u8],
+        offset: usize,
+        expected: impl Into<String>,
+    ) -> ParseError {
+        let offset = offset.min(source.len());
+        let line_start = source[..offset]
+            .iter()
+            .rposition(|ch| *ch == b'\n')
+            .map_or(0, |idx| idx + 1);
+        let line_end = source[offset..]
+            .iter()
+            .position(|ch| *ch == b'\n')
+            .map_or(source.len(), |idx| offset + idx);
+        let line = 1 + source[..line_start]
+            .iter()
+            .filter(|ch| **ch == b'\n')
+            .count();
+        let column = 1 + String::from_utf8_lossy(&source[line_start..offset])
+            .chars()
+            .count();
+        let excerpt = String::from_utf8_lossy(&source[line_start..line_end]);
+        ParseError::new(line, column, &excerpt, expected)
+    }
+
+    /// Converts the error from a parser that was given `source` as its input.
+    pub(crate) fn from_nom<I: AsBytes + Offset + Copy>(
+        source: I,
+        error: nom::Err<VerboseError<I>>,
+    ) -> ParseError {
+        match error {
+            nom::Err::Incomplete(_) => {
+                let source = source.as_bytes();
+                ParseError::at_offset(source, source.len(), "more input")
+            }
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                let offset = error
+                    .errors
+                    .first()
+                    .map_or(0, |(input, _)| source.offset(input));
+                ParseError::at_offset(source.as_bytes(), offset, describe(&error.errors))
+            }
+        }
+    }
+
+    /// Sets the line number for errors from parsers that were only given a single line.
+    pub(crate) fn on_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+
+    pub(crate) fn in_file(mut self, file: &Path) -> ParseError {
+        self.file = Some(file.to_path_buf());
+        self
+    }
+}
+
+fn describe<I>(errors: &[(I, VerboseErrorKind)]) -> String {
+    let context = errors.iter().find_map(|(_, kind)| match kind {
+        VerboseErrorKind::Context(context) => Some(*context),
+        _ => None,
+    });
+    match (context, errors.first()) {
+        (Some(context), _) => context.to_string(),
+        (None, Some((_, VerboseErrorKind::Char(ch)))) => format!("‘{}’", ch),
+        (None, Some((_, VerboseErrorKind::Nom(ErrorKind::Eof)))) => "the end of the input".into(),
+        (None, Some((_, VerboseErrorKind::Nom(ErrorKind::Digit)))) => "a number".into(),
+        (None, Some((_, VerboseErrorKind::Nom(ErrorKind::CrLf)))) => "a line break".into(),
+        (None, Some((_, VerboseErrorKind::Nom(kind)))) => kind.description().to_lowercase(),
+        _ => "something else".into(),
+    }
+}
+
+/// Parses one `item` per line until the end of the input.  Unlike `separated_list1`, a line that
+/// fails to parse is reported as an error instead of ending the list early.
+pub(crate) fn lines_of<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    let end_of_line = context("the end of the line", alt((line_ending, eof)));
+    many0(preceded(not(eof), cut(terminated(item, end_of_line))))
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) if file == Path::new("-") => write!(f, "<stdin>:")?,
+            Some(file) => write!(f, "{}:", file.display())?,
+            None => write!(f, "line ")?,
+        }
+        writeln!(
+            f,
+            "{}:{}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        let caret: String = self
+            .excerpt
+            .chars()
+            .chain(std::iter::repeat(' '))
+            .take(self.column - 1)
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.excerpt)?;
+        write!(f, "{} | {}^", gutter, caret)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use nom::{bytes::complete::tag, character::complete::digit1, sequence::separated_pair};
+
+    use super::*;
+
+    #[test]
+    fn offsets_are_converted_to_lines_and_columns() {
+        let expected_error = ParseError::new(2, 3, "4,x,6", "a number");
+        let error = ParseError::at_offset(b"1,2,3\n4,x,6\n", 8, "a number");
+        assert_eq!(error, expected_error);
+    }
+
+    #[test]
+    fn columns_count_characters_instead_of_bytes() {
+        let expected_error = ParseError::new(1, 3, "αβx", "a number");
+        let error = ParseError::at_offset("αβx".as_bytes(), 4, "a number");
+        assert_eq!(error, expected_error);
+    }
+
+    #[test]
+    fn nom_errors_use_the_innermost_context() {
+        let expected_error = ParseError::new(1, 3, "1,x", "a coordinate");
+        let input = "1,x";
+        let mut parser = separated_pair(digit1, tag(","), context("a coordinate", digit1));
+        let error = parser(input).unwrap_err();
+        assert_eq!(ParseError::from_nom(input, error), expected_error);
+    }
+
+    #[test]
+    fn lines_of_reports_the_line_that_failed() {
+        let expected_error = ParseError::new(3, 1, "x", "a number");
+        let input = "1\n2\nx\n4\n";
+        let error = lines_of(digit1)(input).unwrap_err();
+        assert_eq!(ParseError::from_nom(input, error), expected_error);
+    }
+
+    #[test]
+    fn lines_of_allows_a_missing_final_line_break() {
+        let expected_result = Ok(("", vec!["1", "2"]));
+        let result = lines_of(digit1::<_, VerboseError<_>>)("1\n2");
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let expected_output = concat!(
+            "day7.txt:2:3: expected a number\n",
+            "  |\n",
+            "2 | 4,x,6\n",
+            "  |   ^",
+        );
+        let error = ParseError::new(2, 3, "4,x,6", "a number").in_file(Path::new("day7.txt"));
+        assert_eq!(error.to_string(), expected_output);
+    }
+}
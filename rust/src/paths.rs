@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+
+const APP_DIR: &str = "advent_2021";
+
+fn home_relative(xdg_var: &str, fallback: &str) -> Result<PathBuf> {
+    match env::var_os(xdg_var) {
+        Some(dir) if !dir.is_empty() => Ok(PathBuf::from(dir).join(APP_DIR)),
+        _ => env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(fallback).join(APP_DIR))
+            .ok_or_else(|| anyhow!("Could not find the home directory; set {}.", xdg_var)),
+    }
+}
+
+/// The directory for data that can be recreated (such as downloaded inputs).  It can be overridden
+/// with `ADVENT_2021_CACHE_DIR`.
+pub(crate) fn cache_dir() -> Result<PathBuf> {
+    match env::var_os("ADVENT_2021_CACHE_DIR") {
+        Some(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => home_relative("XDG_CACHE_HOME", ".cache"),
+    }
+}
+
+pub(crate) fn config_dir() -> Result<PathBuf> {
+    home_relative("XDG_CONFIG_HOME", ".config")
+}
+
+/// Where `fetch` stores the input for the given day.
+pub(crate) fn cached_input(cache_dir: &Path, name: &str) -> PathBuf {
+    cache_dir.join("inputs").join(format!("{}.txt", name))
+}
+
+/// Uses the given input or falls back to the day’s cached input when none was given.
+pub(crate) fn input_or_cached(input: Option<&Path>, name: &str) -> Result<PathBuf> {
+    match input {
+        Some(input) => Ok(input.to_path_buf()),
+        None => {
+            let input = cached_input(&cache_dir()?, name);
+            if input.is_file() {
+                Ok(input)
+            } else {
+                Err(anyhow!(
+                    "No input was given, and there is no cached input at {}.  {}",
+                    input.display(),
+                    "Use `fetch` to download it."
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Random puzzle inputs for stress testing the solvers.  The inputs are always in the format the
+//! puzzles use, but they are not guaranteed to have answers (e.g., a diagnostic report whose life
+//! support rating does not converge).
+
+use std::fmt::Write;
+
+use anyhow::{bail, ensure, Result};
+
+/// A SplitMix64 pseudorandom number generator.  It is not suitable for anything but generating
+/// test data, but it is tiny, fast, and generates the same numbers for a seed on every platform.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number from `0` up to (but not including) `bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "the bound must be positive");
+        // Reject the numbers past the last multiple of `bound` to avoid biasing the result.
+        let limit = u64::MAX - u64::MAX % bound;
+        loop {
+            let x = self.next_u64();
+            if x < limit {
+                break x % bound;
+            }
+        }
+    }
+
+    /// Returns a number from `low` to `high` inclusive.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    pub fn shuffle<T>(&mut self, xs: &mut [T]) {
+        for idx in (1..xs.len()).rev() {
+            xs.swap(idx, self.below(idx as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Generates an input for `day` from `seed`.  What `size` means depends on the day, but it is
+/// usually the number of lines (or the width and height of a grid).
+pub fn input(day: u8, seed: u64, size: usize) -> Result<String> {
+    ensure!(size > 0, "The size of the input must be at least 1");
+    let mut rng = SplitMix64::new(seed);
+    let generate = match day {
+        1 => day1,
+        2 => day2,
+        3 => day3,
+        4 => day4,
+        5 => day5,
+        6 => day6,
+        7 => day7,
+        8 => day8,
+        9 => day9,
+        15 => day15,
+        _ => bail!("There is no generator for day {}", day),
+    };
+    let mut out = String::new();
+    generate(&mut rng, size, &mut out)?;
+    Ok(out)
+}
+
+/// Depths that wander up and down like the sea floor.
+fn day1(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    let mut depth = rng.between(100, 200);
+    for _ in 0..size {
+        writeln!(out, "{}", depth)?;
+        depth = (depth + rng.below(21)).saturating_sub(8);
+    }
+    Ok(())
+}
+
+/// Submarine commands that never take it above the surface.
+fn day2(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    let mut depth = 0;
+    for _ in 0..size {
+        let distance = rng.between(1, 9);
+        let command = match rng.below(3) {
+            0 => "forward",
+            1 if depth >= distance => {
+                depth -= distance;
+                "up"
+            }
+            _ => {
+                depth += distance;
+                "down"
+            }
+        };
+        writeln!(out, "{} {}", command, distance)?;
+    }
+    Ok(())
+}
+
+/// A diagnostic report of twelve-bit numbers.
+fn day3(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    for _ in 0..size {
+        writeln!(out, "{:012b}", rng.below(1 << 12))?;
+    }
+    Ok(())
+}
+
+/// Every number from 0 to 99 drawn in a random order followed by `size` boards.
+fn day4(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    let mut numbers: Vec<u8> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let draws = numbers.iter().map(u8::to_string).collect::<Vec<_>>();
+    writeln!(out, "{}", draws.join(","))?;
+    for _ in 0..size {
+        writeln!(out)?;
+        rng.shuffle(&mut numbers);
+        for row in numbers[..25].chunks(5) {
+            let row = row.iter().map(|n| format!("{:2}", n)).collect::<Vec<_>>();
+            writeln!(out, "{}", row.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+/// Horizontal, vertical, and diagonal lines on a 1000 × 1000 grid.
+fn day5(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    const LIMIT: u64 = 999;
+    for _ in 0..size {
+        let (x1, y1) = (rng.below(LIMIT + 1), rng.below(LIMIT + 1));
+        let (x2, y2) = match rng.below(3) {
+            0 => (rng.below(LIMIT + 1), y1),
+            1 => (x1, rng.below(LIMIT + 1)),
+            _ => {
+                let right = rng.below(2) == 0;
+                let down = rng.below(2) == 0;
+                let room_x = if right { LIMIT - x1 } else { x1 };
+                let room_y = if down { LIMIT - y1 } else { y1 };
+                let length = rng.below(room_x.min(room_y) + 1);
+                let x2 = if right { x1 + length } else { x1 - length };
+                let y2 = if down { y1 + length } else { y1 - length };
+                (x2, y2)
+            }
+        };
+        writeln!(out, "{},{} -> {},{}", x1, y1, x2, y2)?;
+    }
+    Ok(())
+}
+
+/// The timers of `size` lanternfish.
+fn day6(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    comma_separated(out, size, || rng.between(1, 5))
+}
+
+/// The positions of `size` crabs.
+fn day7(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    comma_separated(out, size, || rng.below(2000))
+}
+
+/// Displays whose wires are consistently scrambled, so every one of them can be decoded.
+fn day8(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+    fn scramble(rng: &mut SplitMix64, wiring: &[u8], digit: usize) -> String {
+        let mut segments: Vec<u8> = DIGITS[digit]
+            .bytes()
+            .map(|segment| wiring[(segment - b'a') as usize])
+            .collect();
+        rng.shuffle(&mut segments);
+        String::from_utf8(segments).expect("segments are always ASCII")
+    }
+
+    for _ in 0..size {
+        let mut wiring: Vec<u8> = (b'a'..=b'g').collect();
+        rng.shuffle(&mut wiring);
+        let mut patterns: Vec<String> =
+            (0..10).map(|digit| scramble(rng, &wiring, digit)).collect();
+        rng.shuffle(&mut patterns);
+        let output: Vec<String> = (0..4)
+            .map(|_| {
+                let digit = rng.below(10) as usize;
+                scramble(rng, &wiring, digit)
+            })
+            .collect();
+        writeln!(out, "{} | {}", patterns.join(" "), output.join(" "))?;
+    }
+    Ok(())
+}
+
+/// A `size` × `size` heightmap.  Nines are more common than the other heights so that the
+/// heightmap is divided into basins.
+fn day9(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    grid(out, size, || match rng.below(13) {
+        height @ 0..=8 => height,
+        _ => 9,
+    })
+}
+
+/// A `size` × `size` grid of risk levels.
+fn day15(rng: &mut SplitMix64, size: usize, out: &mut String) -> std::fmt::Result {
+    grid(out, size, || rng.between(1, 9))
+}
+
+fn comma_separated(out: &mut String, size: usize, mut f: impl FnMut() -> u64) -> std::fmt::Result {
+    let values = (0..size).map(|_| f().to_string()).collect::<Vec<_>>();
+    writeln!(out, "{}", values.join(","))
+}
+
+fn grid(out: &mut String, size: usize, mut f: impl FnMut() -> u64) -> std::fmt::Result {
+    for _ in 0..size {
+        for _ in 0..size {
+            write!(out, "{}", f())?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{days::*, solution::Solution};
+
+    use super::*;
+
+    #[test]
+    fn splitmix64_generates_the_reference_sequence() {
+        let expected_numbers = [0xe220a8397b1dcdaf, 0x6e789e6aa1b965f4, 0x06c45d188009454f];
+        let mut rng = SplitMix64::new(0);
+        let numbers = [rng.next_u64(), rng.next_u64(), rng.next_u64()];
+        assert_eq!(numbers, expected_numbers);
+    }
+
+    #[test]
+    fn shuffle_keeps_every_element() {
+        let expected_numbers: Vec<u32> = (0..50).collect();
+        let mut numbers = expected_numbers.clone();
+        SplitMix64::new(7).shuffle(&mut numbers);
+        assert_ne!(numbers, expected_numbers);
+        numbers.sort_unstable();
+        assert_eq!(numbers, expected_numbers);
+    }
+
+    #[test]
+    fn the_same_seed_generates_the_same_input() -> Result<()> {
+        for day in [1, 2, 3, 4, 5, 6, 7, 8, 9, 15] {
+            assert_eq!(input(day, 42, 10)?, input(day, 42, 10)?);
+            assert_ne!(input(day, 42, 10)?, input(day, 43, 10)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn generated_inputs_can_be_parsed() -> Result<()> {
+        fn parses(solution: impl Solution, day: u8) -> Result<()> {
+            for seed in 0..5 {
+                let input = input(day, seed, 20)?;
+                solution.parse(&mut input.as_bytes())?;
+            }
+            Ok(())
+        }
+        parses(day1::Day1::default(), 1)?;
+        parses(day2::Day2, 2)?;
+        parses(day3::Day3, 3)?;
+        parses(day4::Day4, 4)?;
+        parses(day5::Day5::default(), 5)?;
+        parses(day6::Day6::default(), 6)?;
+        parses(day7::Day7, 7)?;
+        parses(day8::Day8, 8)?;
+        parses(day9::Day9, 9)?;
+        parses(day15::Day15, 15)
+    }
+
+    #[test]
+    fn days_without_a_generator_are_rejected() {
+        assert!(input(10, 0, 10).is_err());
+        assert!(input(1, 0, 0).is_err());
+    }
+}
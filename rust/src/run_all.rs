@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    io::{stderr, stdout, Write},
+    path::{Path, PathBuf},
+};
+
+use advent_2021::solution::{Parts, Solved};
+use anyhow::{anyhow, Result};
+use clap::{Parser, ValueHint};
+use rayon::prelude::*;
+
+use crate::{commands::Command, config::Config, output::Format};
+
+#[derive(Parser)]
+#[clap(about = "Solve every day using the inputs found in a directory")]
+pub(crate) struct RunAll {
+    /// The directory with each day’s input
+    #[clap(short, long, default_value = "inputs", value_hint = ValueHint::DirPath)]
+    inputs: PathBuf,
+    /// How many days (and parts) to solve at once; defaults to the number of CPUs
+    #[clap(short, long)]
+    jobs: Option<usize>,
+}
+
+/// Finds the input for the given day in `inputs` (e.g., `inputs/day5.txt` for Day 5).  Compressed
+/// inputs (`inputs/day5.txt.gz` or `inputs/day5.txt.zst`) are used when there is no plain one.
+pub(crate) fn input_path(inputs: &Path, name: &str) -> PathBuf {
+    let plain = inputs.join(format!("{}.txt", name));
+    if plain.is_file() {
+        return plain;
+    }
+    ["gz", "zst"]
+        .into_iter()
+        .map(|extension| inputs.join(format!("{}.txt.{}", name, extension)))
+        .find(|path| path.is_file())
+        .unwrap_or(plain)
+}
+
+enum Status {
+    Solved(Solved),
+    Skipped(PathBuf),
+    Failed(anyhow::Error),
+}
+
+impl RunAll {
+    pub(crate) fn run(
+        self,
+        format: Format,
+        time: bool,
+        parts: Parts,
+        config: &Config,
+    ) -> Result<()> {
+        // Days are solved on a pool of their own, so `--jobs` also bounds the parts solved in
+        // parallel within each day.  The parts run one after the other with `--time`, so their
+        // timings aren’t skewed by each other.  The results are still collected in the order of the days.
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs.unwrap_or(0))
+            .build()?;
+        let results: Vec<_> = pool.install(|| {
+            Command::NAMES
+                .par_iter()
+                .map(|name| {
+                    let input = input_path(&self.inputs, name);
+                    let status = if input.is_file() {
+                        match Command::from_input(name, Some(&input), config)
+                            .and_then(|day| day.solve(parts, !time))
+                        {
+                            Ok(solved) => Status::Solved(solved),
+                            Err(error) => Status::Failed(error),
+                        }
+                    } else {
+                        Status::Skipped(input)
+                    };
+                    (*name, status)
+                })
+                .collect()
+        });
+        for (name, status) in &results {
+            if let Status::Skipped(input) = status {
+                eprintln!("Skipping {}: no input found at {}", name, input.display());
+            }
+        }
+
+        let mut out = stdout();
+        if format == Format::Plain {
+            Self::write_summary(&mut out, &results, time)?;
+            Self::write_failures(&mut stderr(), &results)?;
+        } else {
+            format.write_header(&mut out)?;
+            for (name, status) in &results {
+                match status {
+                    Status::Solved(solved) => format.write_answers(&mut out, name, solved, time)?,
+                    Status::Skipped(_) => (),
+                    Status::Failed(error) => format.write_error(&mut out, name, error)?,
+                }
+            }
+        }
+
+        let failures = results
+            .iter()
+            .filter(|(_, status)| matches!(status, Status::Failed(_)))
+            .count();
+        if failures == 0 {
+            Ok(())
+        } else {
+            Err(anyhow!("{} of {} days failed", failures, results.len()))
+        }
+    }
+
+    fn write_summary(out: &mut dyn Write, results: &[(&str, Status)], time: bool) -> Result<()> {
+        writeln!(out, "{:<8}Status", "Day")?;
+        for (name, status) in results {
+            match status {
+                Status::Solved(solved) => {
+                    write!(out, "{:<8}solved:", name)?;
+                    for (idx, (part, answer)) in solved.answers().enumerate() {
+                        let separator = if idx == 0 { "" } else { "," };
+                        write!(out, "{} part {} = {}", separator, part, answer)?;
+                    }
+                    if time {
+                        write!(out, " ({})", solved.timings)?;
+                    }
+                    writeln!(out)?;
+                }
+                Status::Skipped(_) => writeln!(out, "{:<8}skipped (no input)", name)?,
+                // Errors like parse errors span several lines, so only the first one fits in the
+                // table.  The whole error follows it.
+                Status::Failed(error) => {
+                    let message = error.to_string();
+                    let first_line = message.lines().next().unwrap_or_default();
+                    writeln!(out, "{:<8}failed: {}", name, first_line)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes each day that failed with its whole error and what caused it.
+    fn write_failures(out: &mut dyn Write, results: &[(&str, Status)]) -> Result<()> {
+        for (name, status) in results {
+            if let Status::Failed(error) = status {
+                writeln!(out, "{} failed: {}", name, error)?;
+                for cause in error.chain().skip(1) {
+                    writeln!(out, "  caused by: {}", cause)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed() -> Vec<(&'static str, Status)> {
+        let error = anyhow!("Expected a number")
+            .context("Couldn’t parse input.txt at line 2:\n2 | x\n  | ^");
+        vec![
+            ("day1", Status::Skipped(PathBuf::from("inputs/day1.txt"))),
+            ("day2", Status::Failed(error)),
+        ]
+    }
+
+    #[test]
+    fn summaries_keep_a_failure_to_one_line() -> Result<()> {
+        let expected_output = concat!(
+            "Day     Status\n",
+            "day1    skipped (no input)\n",
+            "day2    failed: Couldn’t parse input.txt at line 2:\n",
+        );
+        let mut buf = Vec::new();
+        RunAll::write_summary(&mut buf, &failed(), false)?;
+        assert_eq!(String::from_utf8(buf)?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn failures_are_written_in_full() -> Result<()> {
+        let expected_output = concat!(
+            "day2 failed: Couldn’t parse input.txt at line 2:\n",
+            "2 | x\n",
+            "  | ^\n",
+            "  caused by: Expected a number\n",
+        );
+        let mut buf = Vec::new();
+        RunAll::write_failures(&mut buf, &failed())?;
+        assert_eq!(String::from_utf8(buf)?, expected_output);
+        Ok(())
+    }
+}
@@ -0,0 +1,362 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    fmt,
+    io::{BufRead, Write},
+    iter,
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use crate::{input, parse::ParseError};
+
+/// A single value in an answer, either a number or some text (such as a decoded message).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(i128),
+    Text(String),
+}
+
+/// The answer to one part of a day’s puzzle.  Along with the answer itself, it can carry any
+/// intermediate values that went into it (e.g., the position and depth in Day 2).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub value: Value,
+    pub details: Vec<(&'static str, Value)>,
+}
+
+impl Answer {
+    pub fn with(mut self, name: &'static str, value: impl Into<Value>) -> Answer {
+        self.details.push((name, value.into()));
+        self
+    }
+}
+
+/// A day’s puzzle.  The input is parsed once, then both parts are solved from the parsed input.
+/// The parts are independent of each other, so they can be solved at the same time.
+pub trait Solution: Sync {
+    type Parsed: Sync;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Parsed>;
+    fn part1(&self, parsed: &Self::Parsed) -> Result<Answer>;
+    fn part2(&self, parsed: &Self::Parsed) -> Result<Answer>;
+
+    /// Writes anything besides the answers that was requested by the day’s options.
+    fn extra_output(&self, _parsed: &Self::Parsed, _out: &mut dyn Write) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// How long it took to parse the input and to solve each part.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+/// Which parts of a puzzle to solve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parts {
+    Part1,
+    Part2,
+    Both,
+}
+
+impl Parts {
+    pub fn includes(self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Parts::Part1, 1) | (Parts::Part2, 2) | (Parts::Both, 1 | 2)
+        )
+    }
+}
+
+impl Default for Parts {
+    fn default() -> Self {
+        Parts::Both
+    }
+}
+
+impl FromStr for Parts {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Parts::Part1),
+            "2" => Ok(Parts::Part2),
+            "both" => Ok(Parts::Both),
+            _ => Err(anyhow!("Expected 1, 2, or both but got {}", s)),
+        }
+    }
+}
+
+/// The answers to the parts that were solved.
+pub struct Solved {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    pub timings: Timings,
+}
+
+impl Solved {
+    /// The answer to the given part, if it was solved.
+    pub fn part(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Each part that was solved along with its answer.
+    pub fn answers(&self) -> impl Iterator<Item = (u8, &Answer)> {
+        [(1, &self.part1), (2, &self.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| Some((part, answer.as_ref()?)))
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> Result<T>) -> Result<(T, Duration)> {
+    let start = Instant::now();
+    let result = f()?;
+    Ok((result, start.elapsed()))
+}
+
+/// Like `timed`, but only runs `f` when `enabled` is set.
+fn timed_if<T>(
+    enabled: bool,
+    f: impl FnOnce() -> Result<T>,
+) -> Result<(Option<T>, Option<Duration>)> {
+    if enabled {
+        let (result, duration) = timed(f)?;
+        Ok((Some(result), Some(duration)))
+    } else {
+        Ok((None, None))
+    }
+}
+
+/// Parses the input, noting which file it came from in any parse error.
+fn parse_input<S: Solution>(solution: &S, input: &Path) -> Result<S::Parsed> {
+    let mut reader = input::open(input)?;
+    solution
+        .parse(&mut reader)
+        .map_err(|error| match error.downcast::<ParseError>() {
+            Ok(error) => error.in_file(input).into(),
+            Err(error) => error,
+        })
+}
+
+/// Solves the requested parts, also returning the parsed input for anything else that needs it.
+/// The parts are solved at the same time when `parallel` is set, which makes their timings less
+/// reliable because they compete for the same cores.
+pub fn solve_parsed<S: Solution>(
+    solution: &S,
+    input: &Path,
+    parts: Parts,
+    parallel: bool,
+) -> Result<(S::Parsed, Solved)> {
+    let (parsed, parse) = timed(|| parse_input(solution, input))?;
+    let part1 = || timed_if(parts.includes(1), || solution.part1(&parsed));
+    let part2 = || timed_if(parts.includes(2), || solution.part2(&parsed));
+    let (part1, part2) = if parallel {
+        rayon::join(part1, part2)
+    } else {
+        (part1(), part2())
+    };
+    let ((part1, part1_time), (part2, part2_time)) = (part1?, part2?);
+    let timings = Timings {
+        parse,
+        part1: part1_time,
+        part2: part2_time,
+    };
+    Ok((
+        parsed,
+        Solved {
+            part1,
+            part2,
+            timings,
+        },
+    ))
+}
+
+pub fn solve<S: Solution>(
+    solution: &S,
+    input: &Path,
+    parts: Parts,
+    parallel: bool,
+) -> Result<Solved> {
+    let (_, solved) = solve_parsed(solution, input, parts, parallel)?;
+    Ok(solved)
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Text(value) => f.write_str(value),
+        }
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse {:?}", self.parse)?;
+        if let Some(part1) = self.part1 {
+            write!(f, ", part 1 {:?}", part1)?;
+        }
+        if let Some(part2) = self.part2 {
+            write!(f, ", part 2 {:?}", part2)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)?;
+        if let Some(((first_name, first_value), rest)) = self.details.split_first() {
+            write!(f, " ({}: {}", first_name, first_value)?;
+            for (name, value) in rest {
+                write!(f, ", {}: {}", name, value)?;
+            }
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Integer(value) => serializer.serialize_i128(*value),
+            Value::Text(value) => serializer.serialize_str(value),
+        }
+    }
+}
+
+impl Timings {
+    /// Each phase’s name and how long it took, in the order they ran.  Parts that were not solved
+    /// are left out.
+    pub fn phases(&self) -> impl Iterator<Item = (&'static str, Duration)> {
+        let parts = [("1", self.part1), ("2", self.part2)];
+        iter::once(("parse", self.parse)).chain(
+            parts
+                .into_iter()
+                .filter_map(|(name, duration)| Some((name, duration?))),
+        )
+    }
+}
+
+impl Serialize for Timings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("parse", &self.parse.as_secs_f64())?;
+        if let Some(part1) = self.part1 {
+            map.serialize_entry("part1", &part1.as_secs_f64())?;
+        }
+        if let Some(part2) = self.part2 {
+            map.serialize_entry("part2", &part2.as_secs_f64())?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.details.len() + 1))?;
+        map.serialize_entry("answer", &self.value)?;
+        for (name, value) in &self.details {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+macro_rules! impl_from_integer {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl From<$t> for Value {
+                fn from(value: $t) -> Self {
+                    // Only a `u128` can be too large, and it is kept as text instead of wrapping.
+                    i128::try_from(value)
+                        .map_or_else(|_| Value::Text(value.to_string()), Value::Integer)
+                }
+            }
+
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer {
+                        value: value.into(),
+                        details: Vec::new(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer {
+            value: value.into(),
+            details: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_display_their_value() {
+        let expected_output = "42";
+        let answer = Answer::from(42);
+        assert_eq!(answer.to_string(), expected_output);
+    }
+
+    #[test]
+    fn answers_display_their_details() {
+        let expected_output = "150 (position: 15, depth: 10)";
+        let answer = Answer::from(150).with("position", 15).with("depth", 10);
+        assert_eq!(answer.to_string(), expected_output);
+    }
+
+    #[test]
+    fn answers_too_large_for_an_i128_are_not_wrapped() {
+        let expected_output = "340282366920938463463374607431768211455";
+        let answer = Answer::from(u128::MAX);
+        assert_eq!(answer.to_string(), expected_output);
+    }
+
+    #[test]
+    fn parts_parse_from_their_names() {
+        let expected_parts = [Parts::Part1, Parts::Part2, Parts::Both];
+        let parts = ["1", "2", "both"].map(|name| name.parse::<Parts>().ok());
+        assert_eq!(parts, expected_parts.map(Some));
+        assert!("3".parse::<Parts>().is_err());
+    }
+
+    #[test]
+    fn timings_display_only_the_solved_parts() {
+        let expected_output = "parse 1ms, part 2 2ms";
+        let timings = Timings {
+            parse: Duration::from_millis(1),
+            part1: None,
+            part2: Some(Duration::from_millis(2)),
+        };
+        assert_eq!(timings.to_string(), expected_output);
+    }
+}
@@ -0,0 +1,353 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use advent_2021::solution::{Parts, Value};
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, ValueHint};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Client, ClientOptions},
+    commands::Command,
+    config::Config,
+    fetch::parse_day,
+    paths,
+};
+
+#[derive(Parser)]
+#[clap(about = "Submit the answer to one part of a day’s puzzle")]
+pub(crate) struct Submit {
+    /// The day to submit (e.g., `5` or `day5`)
+    #[clap(parse(try_from_str = parse_day))]
+    day: u8,
+    #[clap(possible_values = ["1", "2"])]
+    part: u8,
+    /// The input to solve; defaults to the cached input for the day
+    #[clap(value_hint = ValueHint::FilePath)]
+    input: Option<PathBuf>,
+    #[clap(flatten)]
+    client: ClientOptions,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    WrongLevel,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Attempt {
+    day: u8,
+    part: u8,
+    answer: String,
+    outcome: Outcome,
+    timestamp: u64,
+}
+
+/// A submitted answer that wasn’t accepted, with the message saying why.
+#[derive(Debug)]
+pub(crate) struct Rejected {
+    pub(crate) outcome: Outcome,
+    message: String,
+}
+
+/// Every answer submitted so far, stored as one JSON object per line.
+struct SubmissionLog {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Submit {
+    pub(crate) fn run(self, config: &Config) -> Result<()> {
+        let name = format!("day{}", self.day);
+        let parts = if self.part == 1 {
+            Parts::Part1
+        } else {
+            Parts::Part2
+        };
+        let solved =
+            Command::from_input(&name, self.input.as_deref(), config)?.solve(parts, false)?;
+        let answer = solved
+            .part(self.part)
+            .map(|answer| answer.value.clone())
+            .ok_or_else(|| anyhow!("Part {} was not solved", self.part))?;
+
+        let cache_dir = paths::cache_dir()?;
+        let mut log = SubmissionLog::open(&cache_dir.join("submissions.jsonl"))?;
+        log.check(self.day, self.part, &answer)?;
+
+        let client = Client::new(self.client, cache_dir)?;
+        let url = client.day_url(self.day, "/answer");
+        let level = self.part.to_string();
+        let response = client.post_form(
+            &url,
+            &[("level", level.as_str()), ("answer", &answer.to_string())],
+        )?;
+        let outcome = parse_response(&response)?;
+        log.record(self.day, self.part, &answer, outcome)?;
+
+        println!("{}", check_outcome(outcome, &answer, self.part)?);
+        Ok(())
+    }
+}
+
+/// What to tell the user about `outcome`.  Anything but the right answer is an error, so scripts
+/// can tell from the exit status whether the answer was accepted.
+fn check_outcome(outcome: Outcome, answer: &Value, part: u8) -> Result<String> {
+    let message = match outcome {
+        Outcome::Correct => return Ok(format!("{} is the right answer!", answer)),
+        Outcome::Incorrect => format!("{} is not the right answer.", answer),
+        Outcome::TooHigh => format!("{} is not the right answer; it is too high.", answer),
+        Outcome::TooLow => format!("{} is not the right answer; it is too low.", answer),
+        Outcome::RateLimited => "An answer was submitted too recently; try later.".into(),
+        Outcome::WrongLevel => format!("Part {} is either solved or locked.", part),
+    };
+    Err(Rejected::new(outcome, message).into())
+}
+
+impl Rejected {
+    pub(crate) fn new(outcome: Outcome, message: String) -> Rejected {
+        Rejected { outcome, message }
+    }
+}
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Rejected {}
+
+pub(crate) fn parse_response(response: &str) -> Result<Outcome> {
+    let outcomes = [
+        ("That's the right answer", Outcome::Correct),
+        ("your answer is too high", Outcome::TooHigh),
+        ("your answer is too low", Outcome::TooLow),
+        ("That's not the right answer", Outcome::Incorrect),
+        ("You gave an answer too recently", Outcome::RateLimited),
+        (
+            "You don't seem to be solving the right level",
+            Outcome::WrongLevel,
+        ),
+    ];
+    outcomes
+        .into_iter()
+        .find(|(message, _)| response.contains(message))
+        .map(|(_, outcome)| outcome)
+        .ok_or_else(|| anyhow!("The response to the submission was not recognized."))
+}
+
+impl SubmissionLog {
+    fn open(path: &Path) -> Result<SubmissionLog> {
+        let attempts = match fs::read_to_string(path) {
+            Ok(contents) => contents
+                .lines()
+                .map(|line| Ok(serde_json::from_str(line)?))
+                .collect::<Result<_>>()?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error.into()),
+        };
+        Ok(SubmissionLog {
+            path: path.to_path_buf(),
+            attempts,
+        })
+    }
+
+    /// Refuses answers that are already known to be wrong, either because they were rejected
+    /// before or because they fall outside the bounds given by earlier too high/too low responses.
+    fn check(&self, day: u8, part: u8, answer: &Value) -> Result<()> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part);
+        let rendered = answer.to_string();
+        for attempt in attempts {
+            let bound = match answer {
+                Value::Integer(answer) => attempt.answer.parse::<i128>().ok().map(|x| (*answer, x)),
+                Value::Text(_) => None,
+            };
+            match (attempt.outcome, bound) {
+                (Outcome::Correct, _) => {
+                    bail!(
+                        "Day {} part {} was already solved with {}.",
+                        day,
+                        part,
+                        attempt.answer
+                    )
+                }
+                (Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow, _)
+                    if attempt.answer == rendered =>
+                {
+                    bail!(
+                        "{} was already rejected for day {} part {}.",
+                        rendered,
+                        day,
+                        part
+                    )
+                }
+                (Outcome::TooHigh, Some((answer, high))) if answer >= high => {
+                    bail!("{} is too high; {} was already too high.", answer, high)
+                }
+                (Outcome::TooLow, Some((answer, low))) if answer <= low => {
+                    bail!("{} is too low; {} was already too low.", answer, low)
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, day: u8, part: u8, answer: &Value, outcome: Outcome) -> Result<()> {
+        let attempt = Attempt {
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&attempt)?)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::{stub_client, stub_server};
+
+    fn log_with(attempts: &[(&str, Outcome)]) -> Result<(tempfile::TempDir, SubmissionLog)> {
+        let dir = tempfile::tempdir()?;
+        let mut log = SubmissionLog::open(&dir.path().join("submissions.jsonl"))?;
+        for (answer, outcome) in attempts {
+            log.record(1, 1, &Value::Integer(answer.parse()?), *outcome)?;
+        }
+        Ok((dir, log))
+    }
+
+    #[test]
+    fn responses_are_parsed_into_outcomes() -> Result<()> {
+        let responses = [
+            (
+                "<p>That's the right answer!  You are one gold star closer.</p>",
+                Outcome::Correct,
+            ),
+            (
+                "<p>That's not the right answer; your answer is too high.</p>",
+                Outcome::TooHigh,
+            ),
+            (
+                "<p>That's not the right answer; your answer is too low.</p>",
+                Outcome::TooLow,
+            ),
+            (
+                "<p>That's not the right answer.  If you're stuck…</p>",
+                Outcome::Incorrect,
+            ),
+            (
+                "<p>You gave an answer too recently; you have to wait…</p>",
+                Outcome::RateLimited,
+            ),
+            (
+                "<p>You don't seem to be solving the right level.</p>",
+                Outcome::WrongLevel,
+            ),
+        ];
+        for (response, expected_outcome) in responses {
+            assert_eq!(parse_response(response)?, expected_outcome);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn only_the_right_answer_is_a_success() -> Result<()> {
+        let expected_message = "12 is the right answer!";
+        let answer = Value::Integer(12);
+        assert_eq!(
+            check_outcome(Outcome::Correct, &answer, 1)?,
+            expected_message
+        );
+        let error = check_outcome(Outcome::TooLow, &answer, 1).unwrap_err();
+        let rejected = error.downcast_ref::<Rejected>().unwrap();
+        assert_eq!(rejected.outcome, Outcome::TooLow);
+        assert_eq!(
+            rejected.to_string(),
+            "12 is not the right answer; it is too low."
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unrecognized_responses_are_errors() {
+        assert!(parse_response("<p>Something else entirely.</p>").is_err());
+    }
+
+    #[test]
+    fn the_log_survives_being_reopened() -> Result<()> {
+        let (dir, log) = log_with(&[("42", Outcome::TooLow)])?;
+        let reopened = SubmissionLog::open(&dir.path().join("submissions.jsonl"))?;
+        assert_eq!(reopened.attempts, log.attempts);
+        Ok(())
+    }
+
+    #[test]
+    fn rejected_answers_are_not_resubmitted() -> Result<()> {
+        let (_dir, log) = log_with(&[("42", Outcome::Incorrect)])?;
+        assert!(log.check(1, 1, &Value::Integer(42)).is_err());
+        assert!(log.check(1, 1, &Value::Integer(43)).is_ok());
+        assert!(log.check(1, 2, &Value::Integer(42)).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn answers_outside_the_known_bounds_are_not_resubmitted() -> Result<()> {
+        let (_dir, log) = log_with(&[("10", Outcome::TooLow), ("20", Outcome::TooHigh)])?;
+        assert!(log.check(1, 1, &Value::Integer(5)).is_err());
+        assert!(log.check(1, 1, &Value::Integer(25)).is_err());
+        assert!(log.check(1, 1, &Value::Integer(15)).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn rate_limited_answers_can_be_resubmitted() -> Result<()> {
+        let (_dir, log) = log_with(&[("42", Outcome::RateLimited)])?;
+        assert!(log.check(1, 1, &Value::Integer(42)).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn answers_are_posted_to_the_server() -> Result<()> {
+        let state = tempfile::tempdir()?;
+        let (base_url, server) = stub_server(200, "<p>That's the right answer!</p>");
+        let client = stub_client(base_url, state.path().to_path_buf());
+        let response = client.post_form(
+            &client.day_url(1, "/answer"),
+            &[("level", "1"), ("answer", "7")],
+        )?;
+        let request = server.join().unwrap();
+        assert_eq!(parse_response(&response)?, Outcome::Correct);
+        assert!(request.starts_with("POST /2021/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=7"));
+        Ok(())
+    }
+}
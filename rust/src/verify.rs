@@ -0,0 +1,285 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{stdout, Write},
+    path::{Path, PathBuf},
+};
+
+use advent_2021::solution::{Answer, Parts, Value};
+use anyhow::{anyhow, Result};
+use clap::{Parser, ValueHint};
+use serde::{Deserialize, Serialize};
+
+use crate::{commands::Command, config::Config, output::Format, run_all::input_path};
+
+#[derive(Parser)]
+#[clap(about = "Check the answers for each day against a file of known-correct answers")]
+pub(crate) struct Verify {
+    /// The TOML or JSON file of known-correct answers
+    #[clap(value_hint = ValueHint::FilePath)]
+    answers: PathBuf,
+    /// The directory with each day’s input
+    #[clap(short, long, default_value = "inputs", value_hint = ValueHint::DirPath)]
+    inputs: PathBuf,
+}
+
+/// A recorded answer.  TOML can’t hold integers past an `i64`, so larger ones are written as text
+/// and compared as numbers.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Recorded {
+    Integer(i64),
+    Unsigned(u64),
+    Text(String),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecordedParts {
+    part1: Option<Recorded>,
+    part2: Option<Recorded>,
+}
+
+enum Outcome {
+    Pass,
+    Mismatch(Value, Value),
+    Fail(String),
+}
+
+/// How an `Outcome` is written as JSON.
+#[derive(Serialize)]
+struct Check<'a> {
+    day: &'a str,
+    part: u8,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<&'a Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actual: Option<&'a Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+impl Verify {
+    pub(crate) fn run(self, format: Format, parts: Parts, config: &Config) -> Result<()> {
+        let recorded = Self::read_answers(&self.answers)?;
+
+        let mut outcomes = Vec::new();
+        for (name, recorded_parts) in recorded {
+            let expected: Vec<_> = [(1, recorded_parts.part1), (2, recorded_parts.part2)]
+                .into_iter()
+                .filter(|(part, _)| parts.includes(*part))
+                .collect();
+            let input = input_path(&self.inputs, &name);
+            match Command::from_input(&name, Some(&input), config)
+                .and_then(|day| day.solve(parts, false))
+            {
+                Ok(solved) => {
+                    for (part, expected) in expected {
+                        if let (Some(expected), Some(answer)) = (expected, solved.part(part)) {
+                            let outcome = Self::check(expected.into(), answer);
+                            outcomes.push((name.clone(), part, outcome));
+                        }
+                    }
+                }
+                Err(error) => {
+                    for (part, expected) in expected {
+                        if expected.is_some() {
+                            outcomes.push((name.clone(), part, Outcome::Fail(error.to_string())));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut out = stdout();
+        format.write_header(&mut out)?;
+        for (name, part, outcome) in &outcomes {
+            Self::write_outcome(&mut out, format, name, *part, outcome)?;
+        }
+
+        let regressions = outcomes
+            .iter()
+            .filter(|(_, _, outcome)| !matches!(outcome, Outcome::Pass))
+            .count();
+        if regressions == 0 {
+            if format == Format::Plain {
+                println!("All {} answers passed.", outcomes.len());
+            }
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "{} of {} answers did not pass",
+                regressions,
+                outcomes.len()
+            ))
+        }
+    }
+
+    fn write_outcome(
+        out: &mut dyn Write,
+        format: Format,
+        name: &str,
+        part: u8,
+        outcome: &Outcome,
+    ) -> Result<()> {
+        match (format, outcome) {
+            (Format::Plain, Outcome::Pass) => writeln!(out, "{:<8}part {}  pass", name, part)?,
+            (Format::Plain, Outcome::Mismatch(expected, actual)) => writeln!(
+                out,
+                "{:<8}part {}  mismatch: expected {}, got {}",
+                name, part, expected, actual
+            )?,
+            (Format::Plain, Outcome::Fail(error)) => {
+                writeln!(out, "{:<8}part {}  fail: {}", name, part, error)?
+            }
+            (Format::Json, _) => {
+                let mut check = Check {
+                    day: name,
+                    part,
+                    status: "pass",
+                    expected: None,
+                    actual: None,
+                    error: None,
+                };
+                match outcome {
+                    Outcome::Pass => (),
+                    Outcome::Mismatch(expected, actual) => {
+                        check.status = "mismatch";
+                        check.expected = Some(expected);
+                        check.actual = Some(actual);
+                    }
+                    Outcome::Fail(error) => {
+                        check.status = "fail";
+                        check.error = Some(error);
+                    }
+                }
+                serde_json::to_writer(&mut *out, &check)?;
+                writeln!(out)?;
+            }
+            (Format::Tsv, Outcome::Pass) => writeln!(out, "{}\t{}\tstatus\tpass", name, part)?,
+            (Format::Tsv, Outcome::Mismatch(expected, actual)) => {
+                writeln!(out, "{}\t{}\tstatus\tmismatch", name, part)?;
+                writeln!(out, "{}\t{}\texpected\t{}", name, part, expected)?;
+                writeln!(out, "{}\t{}\tactual\t{}", name, part, actual)?;
+            }
+            (Format::Tsv, Outcome::Fail(error)) => {
+                // Like the answers, each row has to be a single line.
+                let summary = error.lines().next().unwrap_or_default();
+                writeln!(out, "{}\t{}\tstatus\tfail", name, part)?;
+                writeln!(out, "{}\t{}\terror\t{}", name, part, summary)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_answers(path: &Path) -> Result<BTreeMap<String, RecordedParts>> {
+        let contents = fs::read_to_string(path)?;
+        let is_json = path.extension().map_or(false, |ext| ext == "json");
+        if is_json {
+            Ok(serde_json::from_str(&contents)?)
+        } else {
+            Ok(toml::from_str(&contents)?)
+        }
+    }
+
+    fn check(expected: Value, actual: &Answer) -> Outcome {
+        if expected == actual.value {
+            Outcome::Pass
+        } else {
+            Outcome::Mismatch(expected, actual.value.clone())
+        }
+    }
+}
+
+impl From<Recorded> for Value {
+    fn from(recorded: Recorded) -> Self {
+        match recorded {
+            Recorded::Integer(value) => Value::Integer(value.into()),
+            Recorded::Unsigned(value) => Value::Integer(value.into()),
+            Recorded::Text(value) => match value.parse::<i128>() {
+                Ok(number) => Value::Integer(number),
+                Err(_) => Value::Text(value),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_answers_can_be_numbers_or_text() -> Result<()> {
+        let answers: BTreeMap<String, RecordedParts> =
+            toml::from_str("[day1]\npart1 = 7\npart2 = \"ABC\"\n")?;
+        let day1 = &answers["day1"];
+        assert!(matches!(day1.part1, Some(Recorded::Integer(7))));
+        assert!(matches!(day1.part2, Some(Recorded::Text(ref s)) if s == "ABC"));
+        Ok(())
+    }
+
+    #[test]
+    fn recorded_answers_can_be_larger_than_an_i64() -> Result<()> {
+        let expected_part1 = Value::Integer(18_446_744_073_709_551_615);
+        let expected_part2 = Value::Integer(170_141_183_460_469_231_731_687_303_715_884_105_727);
+        let answers: BTreeMap<String, RecordedParts> = serde_json::from_str(
+            r#"{"day6": {"part1": 18446744073709551615,
+                         "part2": "170141183460469231731687303715884105727"}}"#,
+        )?;
+        let mut answers = answers.into_iter();
+        let (_, day6) = answers.next().unwrap();
+        assert_eq!(Value::from(day6.part1.unwrap()), expected_part1);
+        assert_eq!(Value::from(day6.part2.unwrap()), expected_part2);
+        Ok(())
+    }
+
+    #[test]
+    fn matching_answers_pass() {
+        let outcome = Verify::check(Value::Integer(7), &Answer::from(7));
+        assert!(matches!(outcome, Outcome::Pass));
+    }
+
+    fn render(format: Format, outcome: Outcome) -> Result<String> {
+        let mut buf = Vec::new();
+        Verify::write_outcome(&mut buf, format, "day1", 2, &outcome)?;
+        Ok(String::from_utf8(buf)?)
+    }
+
+    #[test]
+    fn mismatches_are_written_in_every_format() -> Result<()> {
+        let expected_plain = "day1    part 2  mismatch: expected 7, got 5\n";
+        let expected_json =
+            "{\"day\":\"day1\",\"part\":2,\"status\":\"mismatch\",\"expected\":7,\"actual\":5}\n";
+        let expected_tsv = concat!(
+            "day1\t2\tstatus\tmismatch\n",
+            "day1\t2\texpected\t7\n",
+            "day1\t2\tactual\t5\n",
+        );
+        let mismatch = || Outcome::Mismatch(Value::Integer(7), Value::Integer(5));
+        assert_eq!(render(Format::Plain, mismatch())?, expected_plain);
+        assert_eq!(render(Format::Json, mismatch())?, expected_json);
+        assert_eq!(render(Format::Tsv, mismatch())?, expected_tsv);
+        Ok(())
+    }
+
+    #[test]
+    fn failures_are_one_row_in_tsv() -> Result<()> {
+        let expected_output =
+            "day1\t2\tstatus\tfail\nday1\t2\terror\tline 1:1: expected a number\n";
+        let error = "line 1:1: expected a number\n  |\n1 | x\n  | ^".to_string();
+        assert_eq!(render(Format::Tsv, Outcome::Fail(error))?, expected_output);
+        Ok(())
+    }
+
+    #[test]
+    fn different_answers_mismatch() {
+        let outcome = Verify::check(Value::Integer(7), &Answer::from(5));
+        assert!(matches!(
+            outcome,
+            Outcome::Mismatch(Value::Integer(7), Value::Integer(5))
+        ));
+    }
+}
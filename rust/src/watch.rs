@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    fs,
+    io::{stderr, stdout, Write},
+    path::Path,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use advent_2021::solution::{Parts, Solution, Solved};
+use anyhow::{ensure, Result};
+
+use crate::{
+    errors,
+    output::{self, Format},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Solves a day, then solves it again every time its input changes.  After the first run, each
+/// run is followed by how its answers differ from the one before it.  The TSV header is only
+/// printed before the first answers.  This only stops when it is interrupted.
+pub(crate) fn watch<S: Solution>(
+    solution: &S,
+    day: &str,
+    input: &Path,
+    format: Format,
+    time: bool,
+    parts: Parts,
+) -> Result<()> {
+    ensure!(
+        input != Path::new("-"),
+        "--watch needs an input file, but the input is stdin"
+    );
+
+    let mut previous: Option<Solved> = None;
+    let mut last_modified = None;
+    loop {
+        let modified = modified_time(input);
+        if modified != last_modified {
+            last_modified = modified;
+            let header = previous.is_none();
+            match output::run(solution, day, input, format, time, parts, header) {
+                Ok(solved) => {
+                    if let Some(previous) = &previous {
+                        // Keep the changes out of the way of anything trying to parse the answers.
+                        if format == Format::Plain {
+                            write_changes(&mut stdout(), previous, &solved)?;
+                        } else {
+                            write_changes(&mut stderr(), previous, &solved)?;
+                        }
+                    }
+                    previous = Some(solved);
+                }
+                Err(error) => errors::report(&mut stderr(), &error, false)?,
+            }
+            eprintln!("Watching {} for changes…", input.display());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// When the input was last modified or `None` if it can’t be read (e.g., while an editor is
+/// replacing it).
+fn modified_time(input: &Path) -> Option<SystemTime> {
+    fs::metadata(input)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn write_changes(out: &mut dyn Write, previous: &Solved, current: &Solved) -> Result<()> {
+    writeln!(out, "Changes since the last run:")?;
+    for part in [1, 2] {
+        match (previous.part(part), current.part(part)) {
+            (Some(before), Some(after)) if before == after => {
+                writeln!(out, "  Part {}: unchanged", part)?
+            }
+            (Some(before), Some(after)) => {
+                writeln!(out, "  Part {}: {} → {}", part, before, after)?
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_2021::solution::{Answer, Timings};
+
+    use super::*;
+
+    fn solved(part1: Answer, part2: Answer) -> Solved {
+        Solved {
+            part1: Some(part1),
+            part2: Some(part2),
+            timings: Timings::default(),
+        }
+    }
+
+    #[test]
+    fn changes_show_the_previous_and_new_answers() -> Result<()> {
+        let expected_output = concat!(
+            "Changes since the last run:\n",
+            "  Part 1: 5 → 12\n",
+            "  Part 2: unchanged\n",
+        );
+        let previous = solved(Answer::from(5), Answer::from(12));
+        let current = solved(Answer::from(12), Answer::from(12));
+        let mut buf = Vec::new();
+        write_changes(&mut buf, &previous, &current)?;
+        assert_eq!(String::from_utf8(buf)?, expected_output);
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Solves every example in `test_data` with the `advent_2021` binary and compares its output with
+//! the expected output next to it.  An example named `test-5.txt` is solved as `day5`, and its
+//! expected output is in `test-5.expected`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{anyhow, ensure, Result};
+
+struct Example {
+    day: String,
+    input: PathBuf,
+    expected: PathBuf,
+}
+
+fn examples(test_data: &Path) -> Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for entry in fs::read_dir(test_data)? {
+        let input = entry?.path();
+        let number = input
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("test-"))
+            .and_then(|name| name.strip_suffix(".txt"));
+        if let Some(number) = number {
+            examples.push(Example {
+                day: format!("day{}", number),
+                expected: input.with_extension("expected"),
+                input,
+            });
+        }
+    }
+    examples.sort_by(|lhs, rhs| lhs.input.cmp(&rhs.input));
+    Ok(examples)
+}
+
+/// Runs the binary in `dir` without any of the environment, so neither an `advent.toml` nor the
+/// `ADVENT_2021_*` variables of whoever runs the tests can change what it does.  Paths given to it
+/// have to be absolute.
+fn advent_2021(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_advent_2021"));
+    command.current_dir(dir).env_clear();
+    command
+}
+
+fn check(dir: &Path, example: &Example) -> Result<()> {
+    let expected_output = fs::read_to_string(&example.expected)
+        .map_err(|err| anyhow!("{}: {}", example.expected.display(), err))?;
+    let output = advent_2021(dir)
+        .arg(&example.day)
+        .arg(&example.input)
+        .output()?;
+    ensure!(
+        output.status.success(),
+        "{} failed: {}",
+        example.day,
+        String::from_utf8_lossy(&output.stderr).trim_end()
+    );
+    let output = String::from_utf8(output.stdout)?;
+    ensure!(
+        output == expected_output,
+        "{} printed\n{}but {} expects\n{}",
+        example.day,
+        output,
+        example.expected.display(),
+        expected_output
+    );
+    Ok(())
+}
+
+#[test]
+fn examples_have_the_expected_output() -> Result<()> {
+    let examples = examples(&Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data"))?;
+    ensure!(!examples.is_empty(), "There are no examples in test_data");
+    let dir = tempfile::tempdir()?;
+    let failures: Vec<String> = examples
+        .iter()
+        .filter_map(|example| check(dir.path(), example).err())
+        .map(|err| err.to_string())
+        .collect();
+    ensure!(failures.is_empty(), "{}", failures.join("\n"));
+    Ok(())
+}
+
+fn run_all(inputs: &Path, jobs: usize) -> Result<String> {
+    let output = advent_2021(inputs)
+        .args([
+            "--format",
+            "tsv",
+            "run-all",
+            "--jobs",
+            &jobs.to_string(),
+            "--inputs",
+        ])
+        .arg(inputs)
+        .output()?;
+    ensure!(
+        output.status.success(),
+        "run-all failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim_end()
+    );
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[test]
+fn run_all_prints_the_days_in_order_however_many_jobs_there_are() -> Result<()> {
+    let inputs = tempfile::tempdir()?;
+    let examples = examples(&Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data"))?;
+    for example in &examples {
+        fs::copy(
+            &example.input,
+            inputs.path().join(format!("{}.txt", example.day)),
+        )?;
+    }
+    // Days come in numeric order, so day10 goes after day9 rather than after day1.
+    let mut expected_days: Vec<_> = examples
+        .iter()
+        .map(|example| example.day.as_str())
+        .collect();
+    expected_days.sort_by_key(|day| day.trim_start_matches("day").parse::<u32>().ok());
+    let sequential = run_all(inputs.path(), 1)?;
+    let mut days: Vec<_> = sequential
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split('\t').next())
+        .collect();
+    days.dedup();
+    assert_eq!(days, expected_days);
+    assert_eq!(run_all(inputs.path(), 4)?, sequential);
+    Ok(())
+}
+
+#[test]
+fn a_broken_config_only_stops_the_commands_that_use_it() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::write(dir.path().join("advent.toml"), "[day6]\ndays = \n")?;
+    let run = |args: &[&str]| advent_2021(dir.path()).args(args).output();
+    assert!(run(&["--help"])?.status.success());
+    assert!(run(&["completions", "bash"])?.status.success());
+    let output = run(&["day6", "-"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("advent.toml"));
+    Ok(())
+}